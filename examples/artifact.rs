@@ -0,0 +1,26 @@
+use rhook::*;
+use std::process::Command;
+
+// build the hooks once and reuse the library
+fn main() {
+    let library = Command::new("cat")
+        .add_hook(Hook::open(stringify!(|| {
+            let path_name = ManuallyDrop::new(CString::from_raw(path as *mut _));
+            dbg!(&path_name);
+            None
+        })))
+        .build_to("./libcat_hooks.so")
+        .map_err(|e| println!("{}", e))
+        .unwrap();
+
+    // the library can be shipped and attached later without any toolchain
+    let library = HookLibrary::open(library.path()).unwrap();
+    for file in &["Cargo.toml", "README.md"] {
+        library
+            .attach(Command::new("cat").arg(file))
+            .spawn()
+            .unwrap()
+            .wait()
+            .unwrap();
+    }
+}
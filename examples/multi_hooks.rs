@@ -1,17 +1,25 @@
 use rhook::*;
 use std::process::Command;
 
-// return fake data for read
+// hooks on the same function form a chain: the first one added runs first and its
+// `original_read` is the next hook, only the last hook calls the libc read
 fn fake_cat() {
     Command::new("cat")
         .arg("Cargo.toml")
         .add_hooks(vec![
-            Hook::read(stringify!(|| { Some(0) })),
+            Hook::read(stringify!(|| {
+                eprintln!("first hook");
+                // returning None calls the next hook
+                None
+            })),
             Hook::open(stringify!(|| { None })),
         ])
-        .add_hook(Hook::read(stringify!(|| { Some(4) })))
-        .add_hooks(vec![Hook::read(stringify!(|| { Some(3) }))])
-        .add_hook(Hook::read(stringify!(|| { Some(1) })))
+        .add_hook(Hook::read(stringify!(|| {
+            eprintln!("second hook");
+            Some(original_read(fd, buf, count))
+        })))
+        // the last hook pretends the file is empty
+        .add_hook(Hook::read(stringify!(|| { Some(0) })))
         .set_hooks()
         .map_err(|e| println!("{}", e))
         .unwrap()
@@ -21,6 +29,18 @@ fn fake_cat() {
         .unwrap();
 }
 
+// duplicates can be rejected instead
+fn reject_duplicates() {
+    let error = Command::new("cat")
+        .add_hook(Hook::read(stringify!(|| { Some(0) })))
+        .add_hook(Hook::read(stringify!(|| { Some(1) })))
+        .duplicates(Duplicates::Reject)
+        .set_hooks()
+        .unwrap_err();
+    println!("{}", error);
+}
+
 fn main() {
     fake_cat();
+    reject_duplicates();
 }
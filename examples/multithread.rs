@@ -27,7 +27,7 @@ fn multithread() {
                 .spawn()
                 .unwrap()
                 .wait()
-                .unwrap();
+                .unwrap()
         };
     }
     let t1 = std::thread::spawn(|| command!("hello"));
@@ -0,0 +1,18 @@
+use rhook::{hook, RunHook};
+
+// the closure is checked by the compiler of this example, misspelling `count` or returning the
+// wrong type fails here instead of when the hooks are set
+fn main() {
+    std::process::Command::new("cat")
+        .arg("Cargo.toml")
+        .add_hook(hook!(read, |fd, buf, count: size_t| -> ssize_t {
+            let n = original_read(fd, buf, count);
+            eprintln!("read {} bytes from fd {}", n, fd);
+            Some(n)
+        }))
+        .set_hooks()
+        .map_err(|e| println!("{}", e))
+        .unwrap()
+        .status()
+        .unwrap();
+}
@@ -0,0 +1,168 @@
+#!/usr/bin/env bash
+# Print the libc functions that rhook doesn't have yet
+#
+# The declarations are taken from the source of the libc crate in the local cargo registry (the
+# version in Cargo.lock), for linux gnu x86_64, and only the functions exported by the installed
+# glibc are kept, the others are macros or inline functions that can't be hooked.
+#
+# The output has two parts: the `libc!` lines to add to src/libcfn.rs and the names to add to the
+# `gen_hooks!` list in src/hook.rs, both sorted like the existing entries. The variadic functions
+# end with a plain `...`, give their extra arguments names and types when they are known (see
+# `open`). The types they use that are not in src/ctypes.rs yet are listed by its tests.
+#
+# Usage: gen_fns/regenerate.sh [path/to/libc.so.6]
+
+set -euo pipefail
+
+root=$(cd "$(dirname "$0")/.." && pwd)
+glibc=${1:-$(gcc -print-file-name=libc.so.6 2>/dev/null || echo /lib/x86_64-linux-gnu/libc.so.6)}
+version=$(grep -A1 '^name = "libc"$' "$root/Cargo.lock" | sed -n 's/^version = "\(.*\)"$/\1/p')
+libc=$(ls -d "${CARGO_HOME:-$HOME/.cargo}"/registry/src/*/"libc-$version" | head -n1)
+
+# the files declaring the functions of linux gnu x86_64, without the other targets and arches
+sources() {
+    local linux=$libc/src/unix/linux_like
+    ls "$libc/src/unix/mod.rs" "$linux/mod.rs" "$linux/linux/mod.rs" "$linux/linux/gnu/mod.rs" \
+        "$linux/linux/gnu/b64/mod.rs"
+    find "$linux/linux/gnu/b64/x86_64" "$linux/linux/arch/generic" \
+        "$libc/src/new/glibc" "$libc/src/new/linux_uapi" "$libc/src/new/common" \
+        -name '*.rs' 2>/dev/null || true
+}
+
+# every library a hook can be loaded next to, their exported functions can be hooked
+exports=$(
+    for lib in "$glibc" "$(dirname "$glibc")"/lib{m,resolv,rt,util,dl,pthread,crypt}.so.*; do
+        [ -f "$lib" ] && nm -D --defined-only "$lib" | awk '$2 == "T" || $2 == "W" || $2 == "i" { print $3 }'
+    done | sed 's/@.*//' | sort -u
+)
+
+known=$(sed -n 's/^libc!(fn \([A-Za-z0-9_]*\)(.*/\1/p' "$root/src/libcfn.rs" | sort -u)
+
+# one line per declaration, `pub fn name(args) -> ret`. The attributes are evaluated for linux
+# gnu x86_64: a declaration is skipped when its `cfg` is false there, or when a `link_name` applies
+# there because its symbol isn't its name. The `link_name` of other targets (`open$UNIX2003`)
+# don't matter
+declarations=$(
+    sources | xargs cat | awk '
+        BEGIN {
+            split("target_os=linux target_env=gnu target_arch=x86_64 target_family=unix " \
+                "target_pointer_width=64 target_endian=little target_vendor=unknown unix", t, " ")
+            for (i in t) target[t[i]] = 1
+        }
+
+        # a tiny parser of cfg predicates, `src` is read from `pos`
+        function space() { while (substr(src, pos, 1) ~ /[ \t]/) pos++ }
+        function peek() { space(); return substr(src, pos, 1) }
+        function word(    start) {
+            space()
+            start = pos
+            while (substr(src, pos, 1) ~ /[A-Za-z0-9_]/) pos++
+            return substr(src, start, pos - start)
+        }
+        function string(    start) {
+            space()
+            start = ++pos
+            while (pos <= length(src) && substr(src, pos, 1) != "\"") pos++
+            return substr(src, start, pos++ - start)
+        }
+        function predicate(    name, value, result, first) {
+            name = word()
+            if (peek() == "=") {
+                pos++
+                value = string()
+                return (name "=" value) in target
+            }
+            if (peek() != "(") return name in target
+            pos++
+            result = name == "all"
+            for (first = 1; peek() != ")" && pos <= length(src); first = 0) {
+                value = predicate()
+                if (name == "all") result = result && value
+                else if (name == "any") result = result || value
+                else result = !value
+                if (peek() == ",") pos++
+            }
+            pos++
+            return result
+        }
+        # whether the attribute `attr` (`#[...]` on one line) hides the declaration
+        function hides(attr) {
+            if (attr ~ /^#\[link_name/) return 1
+            if (attr ~ /^#\[cfg\(/) {
+                src = substr(attr, 7)
+                pos = 1
+                return !predicate()
+            }
+            if (attr ~ /^#\[cfg_attr\(/ && attr ~ /link_name/) {
+                src = substr(attr, 12)
+                pos = 1
+                return predicate()
+            }
+            return 0
+        }
+
+        {
+            line = $0
+            sub(/\/\/.*/, "", line)
+            gsub(/^[ \t]+|[ \t]+$/, "", line)
+        }
+        # an attribute can span several lines, it ends when its brackets are closed
+        !inside && (attr != "" || line ~ /^#\[/) {
+            attr = attr (attr == "" || attr ~ /\($/ ? "" : " ") line
+            depth = gsub(/\[/, "[", attr) - gsub(/\]/, "]", attr)
+            if (depth == 0) {
+                if (hides(attr)) skip = 1
+                attr = ""
+            }
+            next
+        }
+        line ~ /^pub (unsafe )?fn / && !inside {
+            inside = 1
+            decl = ""
+        }
+        inside {
+            decl = decl (decl ~ /\($/ || decl == "" ? "" : " ") line
+            if (line ~ /;$/ || line ~ /\{$/) {
+                inside = 0
+                # a body means a function defined in Rust, not an import
+                if (line ~ /;$/ && !skip) print decl
+                skip = 0
+            }
+            next
+        }
+        { skip = 0 }
+    '
+)
+
+new=$(
+    echo "$declarations" | sed -n 's/^pub \(unsafe \)\?fn \([A-Za-z0-9_]*\)(\(.*\))\( -> \(.*\)\)\?;$/\2\t\3\t\5/p' |
+        awk -F'\t' -v exports="$exports" -v known="$known" '
+        BEGIN {
+            split(exports, e, "\n"); for (i in e) exported[e[i]] = 1
+            split(known, k, "\n"); for (i in k) have[k[i]] = 1
+        }
+        $1 in exported && !($1 in have) && !($1 in seen) {
+            seen[$1] = 1
+            # the types are resolved in the hook library, which imports libc::*
+            args = $2
+            ret = $3 == "" ? "()" : $3
+            gsub(/(crate|super)::/, "", args)
+            gsub(/(crate|super)::/, "", ret)
+            sub(/,[ ]*$/, "", args)
+            # the libc! macro needs a name for every parameter
+            n = split(args, parts, ", ")
+            args = ""
+            for (i = 1; i <= n; i++) {
+                part = parts[i]
+                if (part ~ /^_:/) sub(/^_/, "arg" i, part)
+                args = args (i > 1 ? ", " : "") part
+            }
+            printf "%s\tlibc!(fn %s(%s) -> %s);\n", $1, $1, args, ret
+        }' | LC_ALL=C sort
+)
+
+echo "// src/libcfn.rs"
+echo "$new" | cut -f2
+echo
+echo "// src/hook.rs"
+echo "$new" | cut -f1
@@ -0,0 +1,91 @@
+//!Compile [rhook](https://docs.rs/rhook) hooks from a build script
+//!
+//!Compiling hooks at runtime needs a Rust toolchain on every machine that runs the program, and the
+//!first [set_hooks](rhook::Anchor::set_hooks) call takes seconds. With this crate the hook library
+//!is compiled with the program and embedded in it.
+//!
+//!## Usage
+//!
+//!1- Add `rhook-build` to the `[build-dependencies]` and compile the hooks in `build.rs`
+//!```no_run
+//!use rhook::Hook;
+//!
+//!fn main() {
+//!    rhook_build::compile(
+//!        "cat_hooks",
+//!        vec![Hook::open(stringify!(|| {
+//!            let path_name = ManuallyDrop::new(CString::from_raw(path as *mut _));
+//!            dbg!(&path_name);
+//!            None
+//!        }))],
+//!    )
+//!    .unwrap();
+//!}
+//!```
+//!
+//!2- Embed the library with [rhook::embed] and attach it to commands
+//!```ignore
+//!let hooks = rhook::embed!("cat_hooks").unwrap();
+//!hooks.attach(Command::new("cat").arg("Cargo.toml")).status().unwrap();
+//!```
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+use rhook::{Anchor, Hook, Profile, RunHook};
+
+/// Compile `hooks` to a library called `name` inside the build script `OUT_DIR`
+///
+/// The library is built with the profile of the crate being built, use [compile_with] to change
+/// the build options
+pub fn compile(name: &str, hooks: Vec<Hook>) -> rhook::Result<()> {
+    compile_with(name, hooks, |_| ())
+}
+
+/// Like [compile], `configure` can change the build options with the [Anchor] builder methods
+///
+/// The library is compiled for the host, cross-compiling (a `TARGET` different from the `HOST`) is
+/// an error
+pub fn compile_with(
+    name: &str,
+    hooks: Vec<Hook>,
+    configure: impl FnOnce(&mut Anchor),
+) -> rhook::Result<()> {
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect(
+        "OUT_DIR is not set, rhook_build::compile should be called from a build script",
+    ));
+    // the hook library is built for the machine running the build script, embedding it in a
+    // program for another target would fail at runtime
+    let (host, target) = (std::env::var("HOST"), std::env::var("TARGET"));
+    if let (Ok(host), Ok(target)) = (host, target) {
+        if host != target {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "rhook_build can't cross-compile the hook library for {} on {}",
+                    target, host
+                ),
+            )
+            .into());
+        }
+    }
+
+    // the hooks are only built, this command never runs
+    let mut command = Command::new(name);
+    let mut anchor = command.add_hooks(hooks);
+    // keep the build inside the target directory, `cargo clean` removes it
+    anchor.workspace(out_dir.join("rhook"));
+    if std::env::var("PROFILE").as_deref() == Ok("release") {
+        anchor.profile(Profile::Release);
+    }
+    configure(&mut anchor);
+
+    let library = anchor.build_to(out_dir.join(format!("{}.so", name)))?;
+    for warning in library.warnings() {
+        // the first line is the short form, the rest is the compiler output
+        let warning = warning.to_string();
+        println!("cargo:warning={}", warning.lines().next().unwrap_or_default());
+    }
+    Ok(())
+}
@@ -0,0 +1,366 @@
+//!Procedural macros of [rhook](https://docs.rs/rhook), use them through the `rhook` crate
+//!
+//!The macros are called by the `macro_rules` wrappers in rhook, which pass `$crate` as the first
+//!token so the generated code doesn't depend on the name rhook is imported with
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Expand `$crate; name, |args| -> return_type { body }` to a [Hook] and a function that
+/// type-checks the closure against the libc signature
+///
+/// The name can be preceded by `before` or `after` for the hooks created by `Hook::before` and
+/// `Hook::after`.
+/// The closure doesn't end up in the hook library as it is: its arguments and return type are
+/// only checked, the hook body is `|| body` like hooks written with `stringify!`
+///
+/// [Hook]: https://docs.rs/rhook/latest/rhook/struct.Hook.html
+#[proc_macro]
+pub fn hook(input: TokenStream) -> TokenStream {
+    match Hook::parse(input) {
+        Ok(hook) => hook.expand(),
+        Err((span, message)) => compile_error(span, &message),
+    }
+}
+
+struct Hook {
+    krate: TokenStream,
+    /// `before` or `after`, see `Hook::before` and `Hook::after`
+    kind: Option<Ident>,
+    name: Ident,
+    args: Vec<Arg>,
+    /// the closure ignores the remaining arguments with `..`
+    rest: bool,
+    return_type: Option<TokenStream>,
+    body: TokenStream,
+}
+
+struct Arg {
+    name: Ident,
+    ty: Option<TokenStream>,
+}
+
+type ParseResult<T> = Result<T, (Span, String)>;
+
+impl Hook {
+    fn parse(input: TokenStream) -> ParseResult<Self> {
+        let mut tokens = input.into_iter().peekable();
+
+        let mut krate = Vec::new();
+        for token in tokens.by_ref() {
+            if is_punct(&token, ';') {
+                break;
+            }
+            krate.push(token);
+        }
+
+        let mut kind = None;
+        let mut name = match tokens.next() {
+            Some(TokenTree::Ident(name)) => name,
+            token => return Err(expected(token.as_ref(), "the name of the libc function")),
+        };
+        if name.to_string() == "before" || name.to_string() == "after" {
+            if let Some(TokenTree::Ident(function)) = tokens.peek() {
+                let function = function.clone();
+                tokens.next();
+                kind = Some(std::mem::replace(&mut name, function));
+            }
+        }
+        match tokens.next() {
+            Some(token) if is_punct(&token, ',') => (),
+            token => return Err(expected(token.as_ref(), "`,` after the function name")),
+        }
+        match tokens.next() {
+            Some(token) if is_punct(&token, '|') => (),
+            token => return Err(expected(token.as_ref(), "a closure")),
+        }
+
+        // the arguments are separated by commas outside of angle brackets, a type can contain
+        // commas (`Option<fn(c_int, c_int)>`) but not `|`
+        let mut args = Vec::new();
+        let mut rest = false;
+        let mut arg = Vec::new();
+        let mut depth = 0;
+        loop {
+            let token = match tokens.next() {
+                Some(token) => token,
+                None => return Err((Span::call_site(), "unterminated closure arguments".into())),
+            };
+            let end = is_punct(&token, '|');
+            if end || (depth == 0 && is_punct(&token, ',')) {
+                if !arg.is_empty() {
+                    match parse_arg(std::mem::take(&mut arg))? {
+                        Some(arg) if !rest => args.push(arg),
+                        Some(arg) => return Err((arg.name.span(), "`..` must come last".into())),
+                        None => rest = true,
+                    }
+                }
+                if end {
+                    break;
+                }
+                continue;
+            }
+            if is_punct(&token, '<') {
+                depth += 1;
+            } else if is_punct(&token, '>') && !arg.last().is_some_and(|t| is_punct(t, '-')) {
+                depth -= 1;
+            }
+            arg.push(token);
+        }
+
+        let mut rest_tokens: Vec<TokenTree> = tokens.collect();
+        let return_type = if rest_tokens.len() > 1
+            && is_punct(&rest_tokens[0], '-')
+            && is_punct(&rest_tokens[1], '>')
+        {
+            // like in closures, a return type needs a block body
+            let body = match rest_tokens.pop() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+                token => return Err(expected(token.as_ref(), "a block after the return type")),
+            };
+            let return_type = rest_tokens.drain(2..).collect();
+            rest_tokens = vec![TokenTree::Group(body)];
+            Some(return_type)
+        } else {
+            None
+        };
+        if rest_tokens.is_empty() {
+            return Err((Span::call_site(), "expected the closure body".into()));
+        }
+
+        Ok(Hook {
+            krate: krate.into_iter().collect(),
+            kind,
+            name,
+            args,
+            rest,
+            return_type,
+            body: rest_tokens.into_iter().collect(),
+        })
+    }
+
+    fn expand(self) -> TokenStream {
+        // the code is written with placeholders, replaced by the tokens of the macro input so they
+        // keep their spans: errors point to the closure and the closure sees its arguments. The
+        // rest uses mixed site spans so the closure can't see the helper variables
+        let mut replacements = vec![
+            ("__krate".to_string(), self.krate.clone()),
+            (
+                "__name".to_string(),
+                TokenTree::Ident(self.name.clone()).into(),
+            ),
+            (
+                "__original".to_string(),
+                TokenTree::Ident(Ident::new(
+                    &format!("original_{}", self.name),
+                    self.name.span(),
+                ))
+                .into(),
+            ),
+            (
+                "__body".to_string(),
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, self.body.clone())).into(),
+            ),
+        ];
+
+        let kind = self.kind.as_ref().map(Ident::to_string);
+        // a before hook rewrites the arguments
+        let mutable = if kind.as_deref() == Some("before") {
+            "mut "
+        } else {
+            ""
+        };
+        let mut fields = Vec::new();
+        let mut bindings = String::new();
+        for (i, arg) in self.args.iter().enumerate() {
+            fields.push(format!("__arg{}", i));
+            replacements.push((
+                format!("__arg{}", i),
+                TokenTree::Ident(arg.name.clone()).into(),
+            ));
+            match &arg.ty {
+                Some(ty) => {
+                    bindings += &format!(
+                        "let {1}__arg{0}: __ty{0} = __krate::__private::arg(__arg{0});",
+                        i, mutable
+                    );
+                    replacements.push((format!("__ty{}", i), ty.clone()));
+                }
+                None => {
+                    bindings += &format!(
+                        "let {1}__arg{0} = __krate::__private::arg(__arg{0});",
+                        i, mutable
+                    )
+                }
+            }
+        }
+        if self.rest {
+            fields.push("..".to_string());
+        }
+        let return_type = match &self.return_type {
+            Some(ty) => {
+                replacements.push(("__return_type".to_string(), ty.clone()));
+                "let _: __krate::__private::PhantomData<fn() -> __return_type> = output;"
+            }
+            None => "",
+        };
+
+        // an after hook sees the result and errno of the original function, the other hooks
+        // return nothing
+        let (call, suffix) = match kind.as_deref() {
+            Some("after") => {
+                for variable in &["result", "errno"] {
+                    replacements.push((
+                        format!("__{}", variable),
+                        TokenTree::Ident(Ident::new(variable, self.name.span())).into(),
+                    ));
+                }
+                (
+                    "let mut __result = __krate::__private::result(output);
+                    let mut __errno: c_int = 0;
+                    let () = (|| __body)();",
+                    ".after()",
+                )
+            }
+            Some(_) => ("let () = (|| __body)();", ".before()"),
+            None => ("__krate::__private::output(output, (|| __body)());", ""),
+        };
+
+        let check = format!(
+            "{{
+                #[allow(warnings, clippy::all)]
+                unsafe fn check(args: __krate::__private::libcfn::__name::Args) {{
+                    use __krate::__private::prelude::*;
+                    let __original = args.original();
+                    let output = args.output();
+                    {return_type}
+                    let __krate::__private::libcfn::__name::Args {{ {fields} }} = args;
+                    {bindings}
+                    {call}
+                }}
+                __krate::Hook::__name
+            }}",
+            return_type = return_type,
+            fields = fields.join(", "),
+            bindings = bindings,
+            call = call,
+        );
+        let check = respan(
+            check.parse().expect("valid check function"),
+            Span::mixed_site(),
+        );
+        let mut expanded = replace(check, &replacements);
+
+        // the hook itself is the body as a string, like hooks written with `stringify!`
+        let body = Literal::string(&format!("|| {}", self.body));
+        let call = Group::new(Delimiter::Parenthesis, TokenTree::Literal(body).into());
+        let mut block = match expanded.into_iter().next() {
+            Some(TokenTree::Group(block)) => block.stream(),
+            _ => unreachable!(),
+        };
+        block.extend(std::iter::once(TokenTree::Group(call)));
+        block.extend(respan(
+            suffix.parse().expect("valid method call"),
+            Span::mixed_site(),
+        ));
+        expanded = TokenTree::Group(Group::new(Delimiter::Brace, block)).into();
+        expanded
+    }
+}
+
+/// Parse a closure argument, `None` for `..`
+fn parse_arg(tokens: Vec<TokenTree>) -> ParseResult<Option<Arg>> {
+    if tokens.len() == 2 && tokens.iter().all(|token| is_punct(token, '.')) {
+        return Ok(None);
+    }
+    let mut tokens = tokens.into_iter();
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        token => return Err(expected(token.as_ref(), "an argument name")),
+    };
+    let ty = match tokens.next() {
+        None => None,
+        Some(token) if is_punct(&token, ':') => Some(tokens.collect()),
+        token => {
+            return Err(expected(
+                token.as_ref(),
+                "`:` or `,` after the argument name",
+            ))
+        }
+    };
+    Ok(Some(Arg { name, ty }))
+}
+
+/// Give every token of `stream` the span `span`
+fn respan(stream: TokenStream, span: Span) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+/// Replace the placeholder identifiers of `stream` with their tokens
+fn replace(stream: TokenStream, replacements: &[(String, TokenStream)]) -> TokenStream {
+    let mut replaced = TokenStream::new();
+    for token in stream {
+        match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                match replacements
+                    .iter()
+                    .find(|(placeholder, _)| *placeholder == name)
+                {
+                    Some((_, tokens)) => replaced.extend(tokens.clone()),
+                    None => replaced.extend(std::iter::once(TokenTree::Ident(ident))),
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut group_replaced =
+                    Group::new(group.delimiter(), replace(group.stream(), replacements));
+                group_replaced.set_span(group.span());
+                replaced.extend(std::iter::once(TokenTree::Group(group_replaced)));
+            }
+            token => replaced.extend(std::iter::once(token)),
+        }
+    }
+    replaced
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == c)
+}
+
+fn expected(token: Option<&TokenTree>, what: &str) -> (Span, String) {
+    match token {
+        Some(token) => (
+            token.span(),
+            format!("expected {}, found `{}`", what, token),
+        ),
+        None => (Span::call_site(), format!("expected {}", what)),
+    }
+}
+
+/// `compile_error!(message)` pointing to `span`
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    group.set_span(span);
+    vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ]
+    .into_iter()
+    .collect()
+}
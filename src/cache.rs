@@ -0,0 +1,370 @@
+//! Inspect and clean the hook libraries cached in the workspace
+//!
+//! Every hook set gets its own entry in the workspace (see [Anchor::workspace](crate::Anchor::workspace)),
+//! nothing is ever removed automatically, use [prune] or [clear] to control the disk usage
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! // remove the libraries older than a week, then the oldest ones until the cache fits in 1GB
+//! let removed = rhook::cache::prune(Some(Duration::from_secs(7 * 24 * 3600)), Some(1 << 30)).unwrap();
+//! for entry in removed {
+//!     println!("removed {:?} ({} bytes)", entry.hooks, entry.size);
+//! }
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::lock::WorkspaceLock;
+use crate::workspace;
+
+/// A hook library in the cache
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// The cache key, a hash of the library source and build settings
+    pub key: String,
+    /// The names of the hooked functions
+    pub hooks: Vec<String>,
+    /// The disk space used by the entry: its sources, its library and its build artifacts
+    pub size: u64,
+    /// When the library was built, or when the build was attempted if it failed
+    pub built: SystemTime,
+    /// The directory of the entry
+    pub path: PathBuf,
+}
+
+/// List the hook libraries cached in the default workspace, newest first
+pub fn list() -> io::Result<Vec<CacheEntry>> {
+    list_in(&workspace::resolve(None))
+}
+
+/// Remove the entries older than `max_age`, then the oldest entries until the cache is smaller
+/// than `max_bytes`, in the default workspace
+///
+/// Return the removed entries
+pub fn prune(max_age: Option<Duration>, max_bytes: Option<u64>) -> io::Result<Vec<CacheEntry>> {
+    prune_in(&workspace::resolve(None), max_age, max_bytes)
+}
+
+/// Remove everything rhook created in the default workspace, including the shared build
+/// artifacts
+pub fn clear() -> io::Result<()> {
+    clear_in(&workspace::resolve(None))
+}
+
+/// Like [list] for the given workspace
+pub fn list_in(workspace: &Path) -> io::Result<Vec<CacheEntry>> {
+    let dir = match std::fs::read_dir(workspace) {
+        Ok(dir) => dir,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries = Vec::new();
+    for entry in dir {
+        let entry = entry?;
+        let key = match entry.file_name().into_string() {
+            Ok(key) if is_key(&key) => key,
+            _ => continue,
+        };
+        let path = entry.path();
+        let hooks = std::fs::read_to_string(path.join(HOOKS_FILE))
+            .map(|hooks| hooks.lines().map(ToString::to_string).collect())
+            .unwrap_or_default();
+        let built = std::fs::metadata(library_path(workspace, &key))
+            .or_else(|_| entry.metadata())?
+            .modified()?;
+        let size = size(&path)?
+            + target_artifacts(workspace, &key)?
+                .iter()
+                .map(|artifact| size(artifact))
+                .sum::<io::Result<u64>>()?;
+        entries.push(CacheEntry {
+            key,
+            hooks,
+            size,
+            built,
+            path,
+        });
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.built));
+    Ok(entries)
+}
+
+/// Like [prune] for the given workspace
+pub fn prune_in(
+    workspace: &Path,
+    max_age: Option<Duration>,
+    max_bytes: Option<u64>,
+) -> io::Result<Vec<CacheEntry>> {
+    if !workspace.exists() {
+        return Ok(Vec::new());
+    }
+    // the same checks as a build, never delete in a directory rhook wouldn't build in
+    workspace::check(workspace)?;
+    let _lock = WorkspaceLock::acquire(workspace)?;
+
+    let now = SystemTime::now();
+    let mut total = 0;
+    let mut removed = Vec::new();
+    // newest first, so once the size limit is reached every remaining entry goes
+    for entry in list_in(workspace)? {
+        let too_old = max_age
+            .is_some_and(|max_age| now.duration_since(entry.built).unwrap_or_default() > max_age);
+        let too_big = max_bytes.is_some_and(|max_bytes| total + entry.size > max_bytes);
+        if too_old || too_big {
+            remove(workspace, &entry)?;
+            removed.push(entry);
+        } else {
+            total += entry.size;
+        }
+    }
+    Ok(removed)
+}
+
+/// Like [clear] for the given workspace
+///
+/// Only the entries and the directories shared by the builds are removed, anything else in the
+/// workspace is left alone. The lock file is kept too, other processes might be waiting on it
+pub fn clear_in(workspace: &Path) -> io::Result<()> {
+    if !workspace.exists() {
+        return Ok(());
+    }
+    workspace::check(workspace)?;
+    let _lock = WorkspaceLock::acquire(workspace)?;
+
+    for entry in std::fs::read_dir(workspace)? {
+        let entry = entry?;
+        let created = match entry.file_name().to_str() {
+            Some(name) => is_key(name) || SHARED_DIRS.contains(&name),
+            None => false,
+        };
+        // symlinks are not followed, `remove_dir_all` would fail on them anyway
+        if created && entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// File listing the hooked functions of an entry, one per line
+const HOOKS_FILE: &str = "hooks";
+
+/// The directories shared by every entry: the cargo target directory and the
+/// [embedded](crate::HookLibrary::from_bytes) libraries
+const SHARED_DIRS: &[&str] = &["target", "embedded"];
+
+/// Entries are named after their key, which is 16 hexadecimal digits
+fn is_key(name: &str) -> bool {
+    name.len() == 16 && name.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn remove(workspace: &Path, entry: &CacheEntry) -> io::Result<()> {
+    for artifact in target_artifacts(workspace, &entry.key)? {
+        if artifact.is_dir() {
+            std::fs::remove_dir_all(artifact)?;
+        } else {
+            std::fs::remove_file(artifact)?;
+        }
+    }
+    std::fs::remove_dir_all(&entry.path)
+}
+
+/// The files cargo created for the entry in the shared target directory
+///
+/// They all have the crate name in their file name, and live at most two levels below the profile
+/// directory (`deps/`, `.fingerprint/`, `incremental/`...)
+fn target_artifacts(workspace: &Path, key: &str) -> io::Result<Vec<PathBuf>> {
+    fn walk(dir: &Path, name: &str, depth: u8, found: &mut Vec<PathBuf>) -> io::Result<()> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().contains(name) {
+                found.push(entry.path());
+            } else if depth > 0 && entry.file_type()?.is_dir() {
+                walk(&entry.path(), name, depth - 1, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut found = Vec::new();
+    walk(&workspace.join("target"), &crate::crate_name(key), 2, &mut found)?;
+    Ok(found)
+}
+
+/// The size of a file, or of a directory and everything in it
+fn size(path: &Path) -> io::Result<u64> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// Compute the cache key of a hook library
+///
+/// The key is a hash of everything that ends up in the library: the generated source and the
+/// build settings, so two hook sets share a library if and only if they would build the same one
+pub(crate) fn key<T: AsRef<[u8]>>(parts: &[T]) -> String {
+    // FNV-1a, we need a hash that is stable between runs and rust versions, which rules out
+    // `DefaultHasher`
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for part in parts {
+        // separate the parts so ("ab", "c") and ("a", "bc") don't collide
+        for byte in part.as_ref().iter().chain(&[0]) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Directory of the hook set with the given key, it contains the crate of the set and its built
+/// library
+///
+/// The workspace holds one such directory per hook set plus the target directory they share
+pub(crate) fn entry_dir(workspace: &Path, key: &str) -> PathBuf {
+    workspace.join(key)
+}
+
+/// Path of the cached library for the given key
+pub(crate) fn library_path(workspace: &Path, key: &str) -> PathBuf {
+    entry_dir(workspace, key).join("librhookdyl.so")
+}
+
+/// Move a freshly built library into the cache, along with the names of its hooks
+///
+/// The library is written with [write_atomic](crate::write_atomic), so a concurrent reader never
+/// sees a half written file
+pub(crate) fn store(
+    workspace: &Path,
+    built: &Path,
+    key: &str,
+    hooks: &[&str],
+) -> io::Result<PathBuf> {
+    std::fs::write(
+        entry_dir(workspace, key).join(HOOKS_FILE),
+        hooks.join("\n"),
+    )?;
+    let library = library_path(workspace, key);
+    crate::write_atomic(&library, |tmp| std::fs::copy(built, tmp).map(drop))?;
+    Ok(library)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, File};
+    use std::os::unix::fs::PermissionsExt;
+
+    /// A fresh workspace named after the test
+    fn workspace(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rhook-cache-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        workspace::create(&dir).unwrap();
+        dir
+    }
+
+    /// Add an entry of `size` bytes built `age` ago, with an artifact in the target directory
+    fn add_entry(workspace: &Path, key: &str, size: usize, age: Duration) {
+        fs::create_dir_all(entry_dir(workspace, key)).unwrap();
+        fs::write(entry_dir(workspace, key).join(HOOKS_FILE), "read").unwrap();
+        let library = library_path(workspace, key);
+        fs::write(&library, vec![0; size]).unwrap();
+        File::options()
+            .write(true)
+            .open(&library)
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+        let deps = workspace.join("target").join("debug").join("deps");
+        fs::create_dir_all(&deps).unwrap();
+        fs::write(deps.join(format!("lib{}.so", crate::crate_name(key))), "").unwrap();
+    }
+
+    fn keys(entries: &[CacheEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.key.as_str()).collect()
+    }
+
+    const OLD: &str = "00000000000000aa";
+    const NEW: &str = "00000000000000bb";
+    const DAY: Duration = Duration::from_secs(24 * 3600);
+
+    #[test]
+    fn prune_removes_old_entries_and_their_artifacts() {
+        let workspace = workspace("prune-age");
+        add_entry(&workspace, OLD, 10, 3 * DAY);
+        add_entry(&workspace, NEW, 10, Duration::ZERO);
+
+        let removed = prune_in(&workspace, Some(DAY), None).unwrap();
+        assert_eq!(keys(&removed), [OLD]);
+        assert_eq!(keys(&list_in(&workspace).unwrap()), [NEW]);
+        assert!(target_artifacts(&workspace, OLD).unwrap().is_empty());
+        assert_eq!(target_artifacts(&workspace, NEW).unwrap().len(), 1);
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_entries_that_fit() {
+        let workspace = workspace("prune-size");
+        add_entry(&workspace, OLD, 1000, DAY);
+        add_entry(&workspace, NEW, 1000, Duration::ZERO);
+
+        let removed = prune_in(&workspace, None, Some(1500)).unwrap();
+        assert_eq!(keys(&removed), [OLD]);
+        assert_eq!(keys(&list_in(&workspace).unwrap()), [NEW]);
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn clear_only_removes_what_rhook_created() {
+        let workspace = workspace("clear");
+        add_entry(&workspace, OLD, 10, Duration::ZERO);
+        for dir in SHARED_DIRS {
+            fs::create_dir_all(workspace.join(dir)).unwrap();
+        }
+        fs::write(workspace.join("notes.txt"), "").unwrap();
+        fs::create_dir_all(workspace.join("src")).unwrap();
+
+        clear_in(&workspace).unwrap();
+        let mut left: Vec<_> = fs::read_dir(&workspace)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left, [".lock", "notes.txt", "src"]);
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn refuse_to_clean_an_insecure_workspace() {
+        let workspace = workspace("insecure");
+        add_entry(&workspace, OLD, 10, DAY);
+        fs::set_permissions(&workspace, fs::Permissions::from_mode(0o775)).unwrap();
+
+        assert!(clear_in(&workspace).is_err());
+        assert!(prune_in(&workspace, Some(Duration::ZERO), None).is_err());
+        assert_eq!(keys(&list_in(&workspace).unwrap()), [OLD]);
+        // not even the lock file is created
+        assert!(!workspace.join(".lock").exists());
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+}
@@ -0,0 +1,138 @@
+//! The cargo backend, it generates a crate for the hook library and builds it with cargo
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::SourceMap;
+use crate::options::{Options, Profile};
+use crate::{crate_name, vendor, BuildError, Diagnostic, Error, Result, SCAFFOLD};
+
+// the empty workspace table keeps cargo from looking for a parent workspace, the crate can live
+// inside another project (in a build script OUT_DIR for example)
+const CARGO_TOML: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "{edition}"
+[lib]
+crate-type = ["dylib"]
+[dependencies]
+libc = {libc}
+[workspace]"#;
+
+/// The Cargo.toml of the hook library, with the crate name still to be filled
+///
+/// libc is taken from the local registry when it is there so the build works offline, see
+/// [vendor::libc_source]
+pub(crate) fn cargo_toml(options: &Options) -> String {
+    let libc = match vendor::libc_source() {
+        Some(path) => format!("{{ path = {:?} }}", path),
+        None => r#""0.2.92""#.to_string(),
+    };
+    let mut cargo_toml = CARGO_TOML
+        .replace("{edition}", options.edition())
+        .replace("{libc}", &libc);
+    if let Some(opt_level) = &options.opt_level {
+        let profile = match options.profile {
+            Profile::Debug => "dev",
+            Profile::Release => "release",
+        };
+        // opt-level is either a number or a string
+        let opt_level = match opt_level.parse::<u8>() {
+            Ok(level) => level.to_string(),
+            Err(_) => format!("{:?}", opt_level),
+        };
+        cargo_toml += &format!("\n[profile.{}]\nopt-level = {}", profile, opt_level);
+    }
+    cargo_toml
+}
+
+/// Create the dynamic library crate and write the scaffold to it, return the path of its source
+pub(crate) fn prepare(dir: &Path, cargo_toml: &str, key: &str) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(dir.join("src"))?;
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        cargo_toml.replace("{name}", &crate_name(key)),
+    )?;
+    let source = dir.join("src").join("lib.rs");
+    std::fs::write(&source, SCAFFOLD)?;
+    Ok(source)
+}
+
+/// Build the dynamic library, and return the path of the built library and the warnings
+pub(crate) fn build_dylib(
+    workspace: &Path,
+    dir: &Path,
+    key: &str,
+    options: &Options,
+    source_map: &SourceMap,
+) -> Result<(PathBuf, Vec<Diagnostic>)> {
+    let warnings = run("b", workspace, dir, key, options, source_map)?;
+    let profile = match options.profile {
+        Profile::Debug => "debug",
+        Profile::Release => "release",
+    };
+    let library = workspace
+        .join("target")
+        .join(profile)
+        .join(format!("lib{}.so", crate_name(key)));
+    Ok((library, warnings))
+}
+
+/// Type-check the dynamic library without building it, return the warnings
+pub(crate) fn check_dylib(
+    workspace: &Path,
+    dir: &Path,
+    key: &str,
+    options: &Options,
+    source_map: &SourceMap,
+) -> Result<Vec<Diagnostic>> {
+    run("check", workspace, dir, key, options, source_map)
+}
+
+/// Run a cargo subcommand on the hook crate, return the compiler messages if it succeeds
+fn run(
+    subcommand: &str,
+    workspace: &Path,
+    dir: &Path,
+    key: &str,
+    options: &Options,
+    source_map: &SourceMap,
+) -> Result<Vec<Diagnostic>> {
+    let mut command = options.command("cargo");
+    command.arg(subcommand);
+    if options.profile == Profile::Release {
+        command.arg("--release");
+    }
+    let output = options.output(
+        "cargo",
+        command
+            .args(["--message-format", "json-diagnostic-rendered-ansi"])
+            .args(["--color", "always"])
+            .current_dir(dir)
+            .env("CARGO_TARGET_DIR", workspace.join("target"))
+            // unlike RUSTFLAGS this one allows spaces inside flags
+            .env("CARGO_ENCODED_RUSTFLAGS", options.rustflags.join("\x1f"))
+            .env_remove("RUSTFLAGS"),
+    )?;
+
+    // cargo prints one json message per line on stdout, only keep the compiler messages about the
+    // hook crate (not the ones about libc)
+    let diagnostics = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| {
+            message["reason"] == "compiler-message"
+                && message["target"]["name"] == crate_name(key).as_str()
+        })
+        .filter_map(|message| source_map.diagnostic(&message["message"]))
+        .collect();
+
+    if output.status.success() {
+        Ok(diagnostics)
+    } else {
+        Err(Error::Build(BuildError {
+            diagnostics,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }))
+    }
+}
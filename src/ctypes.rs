@@ -0,0 +1,301 @@
+//! The C types of the hookable functions, for hook libraries built without the libc crate
+//!
+//! The [rustc](crate::rustc) backend only needs `rustc`, so instead of the libc crate the hook
+//! library gets a `libc` module with these types and the declarations of the functions of
+//! [Signature::all]. The types are the ones of linux gnu x86_64: the aliases are the same as in
+//! libc, and the structs are opaque with the size and alignment of the C struct. The few structs
+//! passed by value only hold integers, so they are passed like the C struct.
+//!
+//! The tests check them against the libc crate, and that every type of the signatures is here
+
+use crate::options::unsafe_attributes;
+use crate::Signature;
+
+macro_rules! ctypes {
+    (
+        aliases { $($alias: ident = $ty: ty;)* }
+        structs { $($name: ident: $size: literal, $align: literal;)* }
+        opaque { $($opaque: ident)* }
+    ) => {
+        const TYPES: &str = concat!(
+            $("pub type ", stringify!($alias), " = ", stringify!($ty), ";\n",)*
+            $(
+                "#[repr(C, align(", $align, "))]\npub struct ", stringify!($name),
+                " {\n    _opaque: [u8; ", $size, "],\n}\n",
+            )*
+            $("pub enum ", stringify!($opaque), " {}\n",)*
+        );
+
+        #[cfg(test)]
+        const NAMES: &[&str] = &[
+            $(stringify!($alias),)*
+            $(stringify!($name),)*
+            $(stringify!($opaque),)*
+        ];
+
+        #[cfg(test)]
+        #[test]
+        fn same_layout_as_libc() {
+            use std::any::type_name;
+            use std::mem::{align_of, size_of};
+            #[allow(unused_imports)]
+            use libc::c_void;
+
+            $(assert_eq!(
+                type_name::<libc::$alias>(),
+                type_name::<$ty>(),
+                stringify!($alias)
+            );)*
+            $(assert_eq!(
+                (size_of::<libc::$name>(), align_of::<libc::$name>()),
+                ($size, $align),
+                stringify!($name)
+            );)*
+            $(assert_eq!(size_of::<libc::$opaque>(), 0, stringify!($opaque));)*
+        }
+    };
+}
+
+ctypes! {
+    aliases {
+        Lmid_t = i64;
+        __priority_which_t = u32;
+        __rlimit_resource_t = u32;
+        c_char = i8;
+        c_double = f64;
+        c_float = f32;
+        c_int = i32;
+        c_long = i64;
+        c_longlong = i64;
+        c_short = i16;
+        c_uchar = u8;
+        c_uint = u32;
+        c_ulong = u64;
+        c_ulonglong = u64;
+        c_ushort = u16;
+        c_void = std::ffi::c_void;
+        clock_t = i64;
+        clockid_t = i32;
+        dev_t = u64;
+        eventfd_t = u64;
+        gid_t = u32;
+        iconv_t = *mut c_void;
+        id_t = u32;
+        idtype_t = u32;
+        in_addr_t = u32;
+        intptr_t = isize;
+        key_t = i32;
+        locale_t = *mut c_void;
+        loff_t = i64;
+        mode_t = u32;
+        mqd_t = i32;
+        nfds_t = u64;
+        nl_item = i32;
+        off64_t = i64;
+        off_t = i64;
+        pid_t = i32;
+        pthread_key_t = u32;
+        pthread_once_t = i32;
+        pthread_spinlock_t = i32;
+        pthread_t = u64;
+        sighandler_t = usize;
+        size_t = usize;
+        socklen_t = u32;
+        speed_t = u32;
+        ssize_t = isize;
+        time_t = i64;
+        uid_t = u32;
+        wchar_t = i32;
+    }
+    structs {
+        Dl_info: 32, 8;
+        addrinfo: 48, 8;
+        aiocb: 168, 8;
+        cmsghdr: 16, 8;
+        cpu_set_t: 128, 8;
+        dirent: 280, 8;
+        dirent64: 280, 8;
+        dl_phdr_info: 64, 8;
+        epoll_event: 12, 1;
+        fd_set: 128, 8;
+        file_handle: 8, 4;
+        fpos64_t: 16, 8;
+        fpos_t: 16, 8;
+        glob64_t: 72, 8;
+        glob_t: 72, 8;
+        group: 32, 8;
+        hostent: 32, 8;
+        if_nameindex: 16, 8;
+        ifaddrs: 56, 8;
+        in_addr: 4, 4;
+        iovec: 16, 8;
+        itimerspec: 32, 8;
+        itimerval: 32, 8;
+        lconv: 96, 8;
+        mallinfo: 40, 4;
+        mallinfo2: 80, 8;
+        mmsghdr: 64, 8;
+        mntent: 40, 8;
+        mq_attr: 64, 8;
+        msghdr: 56, 8;
+        msqid_ds: 120, 8;
+        netent: 24, 8;
+        ntptimeval: 72, 8;
+        passwd: 48, 8;
+        pollfd: 8, 4;
+        posix_spawn_file_actions_t: 80, 8;
+        posix_spawnattr_t: 336, 8;
+        protoent: 24, 8;
+        pthread_attr_t: 56, 8;
+        pthread_barrier_t: 32, 8;
+        pthread_barrierattr_t: 4, 4;
+        pthread_cond_t: 48, 8;
+        pthread_condattr_t: 4, 4;
+        pthread_mutex_t: 40, 8;
+        pthread_mutexattr_t: 4, 4;
+        pthread_rwlock_t: 56, 8;
+        pthread_rwlockattr_t: 8, 8;
+        regex_t: 64, 8;
+        regmatch_t: 8, 4;
+        rlimit: 16, 8;
+        rlimit64: 16, 8;
+        rusage: 144, 8;
+        sched_param: 4, 4;
+        sem_t: 32, 8;
+        sembuf: 6, 2;
+        servent: 32, 8;
+        shmid_ds: 112, 8;
+        sigaction: 152, 8;
+        sigevent: 64, 8;
+        siginfo_t: 128, 8;
+        sigset_t: 128, 8;
+        sigval: 8, 8;
+        sock_extended_err: 16, 4;
+        sockaddr: 16, 2;
+        spwd: 72, 8;
+        stack_t: 24, 8;
+        stat: 144, 8;
+        stat64: 144, 8;
+        statfs: 120, 8;
+        statfs64: 120, 8;
+        statvfs: 112, 8;
+        statvfs64: 112, 8;
+        statx: 256, 8;
+        sysinfo: 112, 8;
+        termios: 60, 4;
+        timespec: 16, 8;
+        timeval: 16, 8;
+        timex: 208, 8;
+        tm: 56, 8;
+        tms: 32, 8;
+        ucontext_t: 968, 8;
+        utimbuf: 16, 8;
+        utmpx: 384, 4;
+        utsname: 390, 1;
+        winsize: 8, 2;
+    }
+    opaque { DIR FILE timezone }
+}
+
+/// The `libc` module of a hook library built with the rustc backend, in the syntax of `edition`
+///
+/// It has what the scaffold and the generated functions use and the declarations of every
+/// hookable function, hooks call them like with the libc crate: `libc::write(..)`
+pub(crate) fn libc_module(edition: &str) -> String {
+    let unsafe_extern = if unsafe_attributes(edition) {
+        "unsafe extern"
+    } else {
+        "extern"
+    };
+    let mut module = String::from(
+        "\n// the part of the libc crate the hooks can use, generated by rhook\npub mod libc {\n",
+    );
+    module += TYPES;
+    module += "pub const RTLD_NEXT: *mut c_void = -1isize as *mut c_void;\n";
+    // the libraries the libc crate links with on linux gnu
+    for library in ["util", "rt", "pthread", "m", "dl"] {
+        module += &format!("#[link(name = \"{}\")]\n", library);
+    }
+    module += unsafe_extern;
+    module += " \"C\" {\n";
+    // the functions the scaffold finds the originals with, they can't be hooked
+    module += &declaration(&crate::libcfn::dlsym::SIGNATURE);
+    module += &declaration(&crate::libcfn::dlvsym::SIGNATURE);
+    for signature in Signature::all() {
+        module += &declaration(signature);
+    }
+    module + "}\n}\n"
+}
+
+/// The declaration of a C function, its variadic parameters are a plain `...`
+fn declaration(signature: &Signature) -> String {
+    let mut params: Vec<String> = signature
+        .params()
+        .iter()
+        .filter(|param| !param.is_variadic())
+        .map(|param| format!("{}: {}", param.name(), param.ty()))
+        .collect();
+    if signature.params().iter().any(|param| param.is_variadic()) {
+        params.push("...".to_string());
+    }
+    format!(
+        "pub fn {}({}) -> {};\n",
+        signature.name(),
+        params.join(", "),
+        signature.ret()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The names of the types used by `ty`, without the parameter names of function pointers
+    fn type_names(ty: &str) -> Vec<&str> {
+        let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let mut names = Vec::new();
+        let mut rest = ty;
+        while let Some(start) = rest.find(is_word) {
+            let end = rest[start..]
+                .find(|c| !is_word(c))
+                .map_or(rest.len(), |end| start + end);
+            let (word, after) = (&rest[start..end], rest[end..].trim_start());
+            let parameter = after.starts_with(':') && !after.starts_with("::");
+            if !parameter && !word.starts_with(|c: char| c.is_ascii_digit()) {
+                names.push(word);
+            }
+            rest = &rest[end..];
+        }
+        names
+    }
+
+    #[test]
+    fn every_type_of_the_signatures_is_declared() {
+        let rust = [
+            "const", "mut", "fn", "unsafe", "extern", "C", "Option", "bool", "u8", "u16", "u32",
+            "u64", "i8", "i16", "i32", "i64", "usize", "isize", "f32", "f64",
+        ];
+        for signature in Signature::all() {
+            let types = signature.params().iter().map(|param| param.ty());
+            for ty in types.chain(Some(signature.ret())) {
+                for name in type_names(ty) {
+                    assert!(
+                        rust.contains(&name) || NAMES.contains(&name),
+                        "{} of {} is not declared",
+                        name,
+                        signature
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn variadic_declaration() {
+        let open = Signature::of("open").unwrap();
+        assert_eq!(
+            declaration(open),
+            "pub fn open(path: *const c_char, oflag: c_int, ...) -> c_int;\n"
+        );
+    }
+}
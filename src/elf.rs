@@ -0,0 +1,201 @@
+//! Read the symbol versions a program is bound to, see [Anchor::detect_versions](crate::Anchor::detect_versions)
+//!
+//! Only 64-bit little-endian ELF files are read, anything else has no versions
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::path::{Path, PathBuf};
+
+const SHT_DYNSYM: u32 = 11;
+const SHT_GNU_VERNEED: u32 = 0x6fff_fffe;
+const SHT_GNU_VERSYM: u32 = 0x6fff_ffff;
+
+/// The version required by the program for each function it imports, `realpath` ->
+/// `GLIBC_2.3` for example
+///
+/// The map is empty when `program` isn't an ELF file (a script) or has no versioned symbols
+pub(crate) fn needed_versions(program: &Path) -> HashMap<String, String> {
+    std::fs::read(program)
+        .ok()
+        .and_then(|elf| Elf { data: &elf }.needed_versions())
+        .unwrap_or_default()
+}
+
+/// Find `program` in `PATH` like the command that runs it
+pub(crate) fn find_program(program: &Path) -> Option<PathBuf> {
+    if program.components().count() > 1 {
+        return Some(program.to_path_buf());
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+struct Elf<'a> {
+    data: &'a [u8],
+}
+
+struct Section {
+    kind: u32,
+    offset: usize,
+    size: usize,
+    link: usize,
+    info: usize,
+}
+
+impl<'a> Elf<'a> {
+    fn bytes(&self, offset: usize, len: usize) -> Option<&'a [u8]> {
+        self.data.get(offset..offset.checked_add(len)?)
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        Some(u16::from_le_bytes(self.bytes(offset, 2)?.try_into().ok()?))
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        Some(u32::from_le_bytes(self.bytes(offset, 4)?.try_into().ok()?))
+    }
+
+    fn u64(&self, offset: usize) -> Option<usize> {
+        u64::from_le_bytes(self.bytes(offset, 8)?.try_into().ok()?)
+            .try_into()
+            .ok()
+    }
+
+    /// The nul terminated string at `offset` in the string table `strtab`
+    fn string(&self, strtab: &Section, offset: usize) -> Option<String> {
+        let start = strtab.offset.checked_add(offset)?;
+        let bytes = self
+            .data
+            .get(start..strtab.offset.checked_add(strtab.size)?)?;
+        let end = bytes.iter().position(|&b| b == 0)?;
+        Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    fn sections(&self) -> Option<Vec<Section>> {
+        // 64-bit little-endian
+        if self.bytes(0, 6)? != b"\x7fELF\x02\x01" {
+            return None;
+        }
+        let offset = self.u64(0x28)?;
+        let entry_size = self.u16(0x3a)? as usize;
+        let count = self.u16(0x3c)? as usize;
+        (0..count)
+            .map(|i| {
+                let header = offset.checked_add(i.checked_mul(entry_size)?)?;
+                let field = |offset: usize| header.checked_add(offset);
+                Some(Section {
+                    kind: self.u32(field(4)?)?,
+                    offset: self.u64(field(24)?)?,
+                    size: self.u64(field(32)?)?,
+                    link: self.u32(field(40)?)? as usize,
+                    info: self.u32(field(44)?)? as usize,
+                })
+            })
+            .collect()
+    }
+
+    fn needed_versions(&self) -> Option<HashMap<String, String>> {
+        let sections = self.sections()?;
+        let find = |kind| sections.iter().find(|section| section.kind == kind);
+        let (dynsym, versym, verneed) = (
+            find(SHT_DYNSYM)?,
+            find(SHT_GNU_VERSYM)?,
+            find(SHT_GNU_VERNEED)?,
+        );
+
+        // the version indexes used by the imported symbols, and their names
+        let mut names = HashMap::new();
+        let strtab = sections.get(verneed.link)?;
+        let mut need = verneed.offset;
+        // the offsets come from the file, a malformed one must not overflow
+        let at = |base: usize, offset: usize| base.checked_add(offset);
+        for _ in 0..verneed.info {
+            let mut aux = at(need, self.u32(at(need, 8)?)? as usize)?;
+            for _ in 0..self.u16(at(need, 2)?)? {
+                let index = self.u16(at(aux, 6)?)?;
+                let name = self.u32(at(aux, 8)?)? as usize;
+                names.insert(index, self.string(strtab, name)?);
+                aux = at(aux, self.u32(at(aux, 12)?)? as usize)?;
+            }
+            need = at(need, self.u32(at(need, 12)?)? as usize)?;
+        }
+
+        let mut versions = HashMap::new();
+        let strtab = sections.get(dynsym.link)?;
+        for i in 0..dynsym.size / 24 {
+            let symbol = at(dynsym.offset, i * 24)?;
+            // only the undefined symbols are imported
+            if self.u16(at(symbol, 6)?)? != 0 {
+                continue;
+            }
+            // the high bit marks a hidden version
+            let index = self.u16(at(versym.offset, i * 2)?)? & 0x7fff;
+            if let Some(version) = names.get(&index) {
+                let name = self.string(strtab, self.u32(symbol)? as usize)?;
+                versions.insert(name, version.clone());
+            }
+        }
+        Some(versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current_exe() -> Vec<u8> {
+        std::fs::read(std::env::current_exe().unwrap()).unwrap()
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", target_env = "gnu"))]
+    fn finds_the_glibc_version_of_an_import() {
+        let versions = needed_versions(&std::env::current_exe().unwrap());
+        assert_eq!(
+            versions.get("malloc").map(String::as_str),
+            Some("GLIBC_2.2.5")
+        );
+        // only the imports are listed
+        assert!(!versions.contains_key("finds_the_glibc_version_of_an_import"));
+    }
+
+    #[test]
+    fn not_an_elf_file_has_no_versions() {
+        assert!(Elf {
+            data: b"#!/bin/sh\n"
+        }
+        .needed_versions()
+        .is_none());
+        assert!(needed_versions(Path::new("/nonexistent")).is_empty());
+    }
+
+    #[test]
+    fn malformed_offsets_return_none() {
+        let elf = current_exe();
+        let sections = Elf { data: &elf }.sections().unwrap();
+        let headers = u64::from_le_bytes(elf[0x28..0x30].try_into().unwrap()) as usize;
+        let entry_size = u16::from_le_bytes(elf[0x3a..0x3c].try_into().unwrap()) as usize;
+
+        // a section offset near the end of the address space
+        for i in 0..sections.len() {
+            let mut elf = elf.clone();
+            let offset = headers + i * entry_size + 24;
+            elf[offset..offset + 8].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+            Elf { data: &elf }.needed_versions();
+        }
+
+        // the section headers near the end of the address space
+        let mut elf = elf.clone();
+        elf[0x28..0x30].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+        assert!(Elf { data: &elf }.needed_versions().is_none());
+    }
+
+    #[test]
+    fn truncated_file_returns_none() {
+        let elf = current_exe();
+        for len in [0, 4, 0x40, elf.len() / 2] {
+            assert!(Elf { data: &elf[..len] }.needed_versions().is_none());
+        }
+    }
+}
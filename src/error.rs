@@ -0,0 +1,272 @@
+use std::fmt;
+use std::io;
+
+use serde_json::Value;
+
+/// The error returned when setting up hooks fails
+#[derive(Debug)]
+pub enum Error {
+    /// The program needed to build the hooks (cargo, rustc or the requested rustup toolchain) is
+    /// not installed
+    ToolchainNotFound(String),
+    /// The hook library failed to compile
+    Build(BuildError),
+    /// Several hooks were added on this function and [Duplicates::Reject](crate::Duplicates) is
+    /// set
+    DuplicateHook(String),
+    /// This function is not exported by the C library, it is a C macro, an inline function or it is
+    /// linked statically, so the hook library can't interpose it
+    Unhookable(String),
+    /// The hook library calls this function to find the original functions (`dlsym` and
+    /// `dlvsym`), a hook on it would call itself
+    Reserved(String),
+    /// Any other I/O error, for example the workspace could not be created
+    Io(io::Error),
+}
+
+/// A failed build of the hook library
+#[derive(Debug)]
+pub struct BuildError {
+    /// The compiler messages, mapped back to the hooks that caused them
+    pub diagnostics: Vec<Diagnostic>,
+    /// The raw output of the compiler, useful when the failure isn't a compiler message (a broken
+    /// manifest for example)
+    pub stderr: String,
+}
+
+/// A compiler message about the generated hook library
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// `error`, `warning`, `note`...
+    pub level: String,
+    pub message: String,
+    /// The name of the function hooked by the [Hook](crate::Hook) the message is about, see
+    /// [Hook::name](crate::Hook::name), `None` if it points outside of any hook (in the scaffold
+    /// for example)
+    pub hook: Option<&'static str>,
+    /// The line inside the hook closure string, starting at 1, `None` if the message points to the
+    /// code generated around the closure
+    pub line: Option<usize>,
+    /// The column inside that line, starting at 1
+    pub column: Option<usize>,
+    /// The message as printed by the compiler, its locations refer to the generated source
+    pub rendered: String,
+}
+
+/// The error returned by [Hook::from_name](crate::Hook::from_name) for a function that can't be
+/// hooked by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFunction {
+    pub name: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ToolchainNotFound(program) => write!(f, "{} is not installed", program),
+            Error::Build(error) => error.fmt(f),
+            Error::DuplicateHook(name) => write!(f, "{} is hooked more than once", name),
+            Error::Unhookable(name) => write!(
+                f,
+                "{} is a C macro, an inline function or linked statically, there is no symbol to hook",
+                name
+            ),
+            Error::Reserved(name) => write!(
+                f,
+                "{} is used by the hook library to find the original functions, it can't be hooked",
+                name
+            ),
+            Error::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.diagnostics.is_empty() {
+            return write!(f, "failed to build the hook library\n{}", self.stderr);
+        }
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.hook, self.line) {
+            (Some(hook), Some(line)) => write!(
+                f,
+                "{} in hook {} at line {}: {}",
+                self.level, hook, line, self.message
+            )?,
+            (Some(hook), None) => write!(f, "{} in hook {}: {}", self.level, hook, self.message)?,
+            _ => write!(f, "{}: {}", self.level, self.message)?,
+        }
+        write!(f, "\n{}", self.rendered)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UnknownFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not a known libc function, Hook::custom can hook it with its signature",
+            self.name
+        )
+    }
+}
+
+impl std::error::Error for UnknownFunction {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// Where each hook ended up in the generated source, used to map compiler messages back to hooks
+#[derive(Debug, Default)]
+pub(crate) struct SourceMap {
+    hooks: Vec<HookLocation>,
+}
+
+#[derive(Debug)]
+struct HookLocation {
+    name: &'static str,
+    /// lines of the generated function, both inclusive and starting at 1
+    start: usize,
+    end: usize,
+    /// position of the first character of the closure
+    body_line: usize,
+    body_column: usize,
+    body_lines: usize,
+}
+
+impl SourceMap {
+    /// Record a function appended to `source`, `source` is the generated code before it
+    pub(crate) fn push(&mut self, source: &str, name: &'static str, body: &str, function: &str) {
+        let start = source.matches('\n').count() + 1;
+        let end = start + function.matches('\n').count();
+        let (body_line, body_column) = match function.find(body) {
+            Some(offset) => {
+                let before = &function[..offset];
+                let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                (
+                    start + before.matches('\n').count(),
+                    before[line_start..].chars().count() + 1,
+                )
+            }
+            None => (0, 0),
+        };
+        self.hooks.push(HookLocation {
+            name,
+            start,
+            end,
+            body_line,
+            body_column,
+            body_lines: body.matches('\n').count() + 1,
+        });
+    }
+
+    /// Convert a rustc JSON diagnostic about the generated source
+    ///
+    /// Messages that don't point to any code (like "aborting due to previous error") are skipped
+    pub(crate) fn diagnostic(&self, message: &Value) -> Option<Diagnostic> {
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+        let mut diagnostic = Diagnostic {
+            level: text(&message["level"]),
+            message: text(&message["message"]),
+            hook: None,
+            line: None,
+            column: None,
+            rendered: text(&message["rendered"]),
+        };
+
+        let primary = message["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true));
+        let (line, column) = match primary {
+            Some(span) => (
+                span["line_start"].as_u64().unwrap_or_default() as usize,
+                span["column_start"].as_u64().unwrap_or_default() as usize,
+            ),
+            None => return None,
+        };
+
+        // a function ends on the line where the next one starts, that line belongs to the next one
+        if let Some(hook) = self
+            .hooks
+            .iter()
+            .rev()
+            .find(|hook| hook.start <= line && line <= hook.end)
+        {
+            diagnostic.hook = Some(hook.name);
+            if hook.body_line != 0
+                && hook.body_line <= line
+                && line < hook.body_line + hook.body_lines
+            {
+                let line = line - hook.body_line + 1;
+                diagnostic.line = Some(line);
+                diagnostic.column = Some(if line == 1 {
+                    column.saturating_sub(hook.body_column) + 1
+                } else {
+                    column
+                });
+            }
+        }
+        Some(diagnostic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(line: usize, column: usize) -> Value {
+        json!({
+            "level": "warning",
+            "message": "unused variable: `x`",
+            "rendered": "",
+            "spans": [{ "is_primary": true, "line_start": line, "column_start": column }],
+        })
+    }
+
+    #[test]
+    fn diagnostic_in_a_multi_line_body() {
+        let scaffold = "use libc::*;\n\n";
+        let body = "|| {\n    let x = 1;\n    None\n}";
+        let function = format!("fn read() {{\n    if let Some(r) = ({})() {{}}\n}}\n", body);
+        let mut source_map = SourceMap::default();
+        source_map.push(scaffold, "read", body, &function);
+
+        // the body starts on line 4 of the generated source, `x` is on its second line
+        let diagnostic = source_map.diagnostic(&message(5, 9)).unwrap();
+        assert_eq!(diagnostic.hook, Some("read"));
+        assert_eq!(diagnostic.line, Some(2));
+        assert_eq!(diagnostic.column, Some(9));
+
+        // on the first line the column is counted from the start of the closure
+        let diagnostic = source_map.diagnostic(&message(4, 26)).unwrap();
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(1), Some(4)));
+
+        // the code generated around the closure belongs to the hook but has no line
+        let diagnostic = source_map.diagnostic(&message(3, 1)).unwrap();
+        assert_eq!((diagnostic.hook, diagnostic.line), (Some("read"), None));
+
+        // the scaffold belongs to no hook
+        let diagnostic = source_map.diagnostic(&message(1, 1)).unwrap();
+        assert_eq!(diagnostic.hook, None);
+    }
+}
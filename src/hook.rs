@@ -1,81 +1,373 @@
+use crate::error::UnknownFunction;
 use crate::libcfn;
+use crate::Signature;
+use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
 
-macro_rules! gen_hook_enum {
-    ($($variant: ident)*) => (
-        #[allow(non_camel_case_types)]
-        #[allow(clippy::upper_case_acronyms)]
-        #[derive(Debug, Eq)]
-        /// libc hooks enum
-        pub enum Hook {
-            $($variant(&'static str),)*
-        }
+/// A libc hook, the function to hook and the closure that replaces it
+///
+/// Create it with the function of the same name (`Hook::read`), with [Hook::from_name] when the
+/// function is only known at runtime, or with [Hook::custom] for functions that are not in the list
+#[derive(Debug, Clone)]
+pub struct Hook {
+    name: &'static str,
+    signature: Cow<'static, Signature>,
+    body: Cow<'static, str>,
+    kind: Kind,
+    /// The function the body is written for when this hooks one of its twins
+    twin_of: Option<Cow<'static, Signature>>,
+    /// The symbol version of the original function, see [Hook::version]
+    version: Option<Cow<'static, str>>,
+}
+
+/// When the body of a hook runs, see [Hook::before] and [Hook::after]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// In place of the original function, the default
+    Replace,
+    Before,
+    After,
+}
+
+macro_rules! gen_hooks {
+    ($($variant: ident)* ; $($unhookable: ident)* ; $($reserved: ident)*) => (
+        /// The signatures of the libc functions that can be hooked
+        static SIGNATURES: &[&Signature] = &[$(&libcfn::$variant::SIGNATURE,)*];
+
+        /// The names of the entries of the libc crate that are not exported functions, hooking
+        /// them would never run the hook
+        static UNHOOKABLE: &[&str] = &[$(stringify!($unhookable),)*];
+
+        /// The names of the functions the hook library calls to find the original functions, a
+        /// hook on them would call itself
+        static RESERVED: &[&str] = &[$(stringify!($reserved),)*];
+
+        #[allow(non_snake_case)]
         impl Hook {
-            pub(crate) fn function(&self, ) -> String {
-                match self {
-                    $(Hook::$variant(fun) => libcfn::$variant(fun),)*
+            $(
+                #[doc = concat!("Hook [libc::", stringify!($variant), "]")]
+                pub fn $variant(body: impl Into<Cow<'static, str>>) -> Hook {
+                    Hook::libc(&libcfn::$variant::SIGNATURE, body.into())
                 }
-            }
-        }
-        impl PartialEq for Hook {
-            fn eq(&self, other: &Hook) -> bool {
-                match (self, other) {
-                    $((Hook::$variant(_), Hook::$variant(_)) => true,)*
-                    _ => false
+            )*
+            $(
+                #[doc = concat!("[libc::", stringify!($unhookable), "] is not a function of the C library, this hook never runs")]
+                #[doc = ""]
+                #[doc = "[set_hooks](crate::Anchor::set_hooks) fails with [Error::Unhookable](crate::Error::Unhookable)"]
+                #[deprecated(note = "this is not exported by the C library, there is no symbol to hook")]
+                pub fn $unhookable(body: impl Into<Cow<'static, str>>) -> Hook {
+                    Hook::libc(&libcfn::$unhookable::SIGNATURE, body.into())
                 }
-            }
+            )*
+            $(
+                #[doc = concat!("[libc::", stringify!($reserved), "] finds the original functions of the hooks, this hook would call itself")]
+                #[doc = ""]
+                #[doc = "[set_hooks](crate::Anchor::set_hooks) fails with [Error::Reserved](crate::Error::Reserved)"]
+                #[deprecated(note = "the hook library calls this function to find the original functions")]
+                pub fn $reserved(body: impl Into<Cow<'static, str>>) -> Hook {
+                    Hook::libc(&libcfn::$reserved::SIGNATURE, body.into())
+                }
+            )*
         }
-        impl Hash for Hook {
-            fn hash<H: Hasher>(&self, state: &mut H) {
-                match self {
-                    $(Hook::$variant(_) => stringify!($variant).hash(state),)*
+
+        impl Signature {
+            /// The signature of the libc function called `name`, `None` if it can't be hooked
+            pub fn of(name: &str) -> Option<&'static Signature> {
+                match name {
+                    $(stringify!($variant) => Some(&libcfn::$variant::SIGNATURE),)*
+                    _ => None,
                 }
             }
         }
     )
 }
 
-gen_hook_enum!(
-CMSG_DATA
-CMSG_FIRSTHDR
-CMSG_LEN
-CMSG_NXTHDR
-CMSG_SPACE
-CPU_ALLOC_SIZE
-CPU_CLR
-CPU_COUNT
-CPU_COUNT_S
-CPU_EQUAL
-CPU_ISSET
-CPU_SET
-CPU_ZERO
-FD_CLR
-FD_ISSET
-FD_SET
-FD_ZERO
-IPOPT_CLASS
-IPOPT_COPIED
-IPOPT_NUMBER
-IPTOS_ECN
-IPTOS_PREC
-IPTOS_TOS
-NLA_ALIGN
-QCMD
-RT_ADDRCLASS
-RT_LOCALADDR
-RT_TOS
-SO_EE_OFFENDER
-WCOREDUMP
-WEXITSTATUS
-WIFCONTINUED
-WIFEXITED
-WIFSIGNALED
-WIFSTOPPED
-WSTOPSIG
-WTERMSIG
-W_EXITCODE
-W_STOPCODE
+impl Hook {
+    fn libc(signature: &'static Signature, body: Cow<'static, str>) -> Hook {
+        Hook {
+            name: signature.name(),
+            signature: Cow::Borrowed(signature),
+            body,
+            kind: Kind::Replace,
+            twin_of: None,
+            version: None,
+        }
+    }
+
+    /// Hook the libc function called `name`, for hooks chosen at runtime
+    ///
+    /// Fails if `name` isn't one of [Hook::names], use [Hook::custom] for those functions
+    /// ```
+    /// use rhook::Hook;
+    ///
+    /// let hook = Hook::from_name("read", "|| Some(0)".to_string()).unwrap();
+    /// assert_eq!(hook.name(), "read");
+    /// assert!(Hook::from_name("not_a_function", "|| None").is_err());
+    /// ```
+    pub fn from_name(
+        name: &str,
+        body: impl Into<Cow<'static, str>>,
+    ) -> Result<Hook, UnknownFunction> {
+        match Signature::of(name) {
+            Some(signature) => Ok(Hook::libc(signature, body.into())),
+            None => Err(UnknownFunction {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// The names of the libc functions that can be hooked, in alphabetical order
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        Signature::all().map(Signature::name)
+    }
+
+    /// Run the body before the original function instead of in place of it
+    ///
+    /// The body is a closure that returns nothing, the arguments are mutable and the original
+    /// function is called with their new values
+    /// ```no_run
+    /// use rhook::{Hook, RunHook};
+    ///
+    /// // read at most 16 bytes at a time
+    /// std::process::Command::new("cat")
+    ///     .add_hook(Hook::read(stringify!(|| count = count.min(16))).before())
+    ///     .set_hooks()
+    ///     .unwrap();
+    /// ```
+    pub fn before(mut self) -> Hook {
+        self.kind = Kind::Before;
+        self
+    }
+
+    /// Run the body after the original function instead of in place of it
+    ///
+    /// The body is a closure that returns nothing, it sees the arguments, the value returned by
+    /// the original function as `result` and the `errno` it set as `errno`. Both are mutable and
+    /// their new values are returned to the caller
+    /// ```no_run
+    /// use rhook::{Hook, RunHook};
+    ///
+    /// // pretend every file is missing
+    /// std::process::Command::new("cat")
+    ///     .add_hook(Hook::open(stringify!(|| if result != -1 {
+    ///         close(result);
+    ///         result = -1;
+    ///         errno = ENOENT;
+    ///     })).after())
+    ///     .set_hooks()
+    ///     .unwrap();
+    /// ```
+    pub fn after(mut self) -> Hook {
+        self.kind = Kind::After;
+        self
+    }
+
+    /// This hook followed by the same hook on the twins of the function, for example `open64`,
+    /// `__open_2` and `__open64_2` for `open`
+    ///
+    /// Programs built with `_FORTIFY_SOURCE` call `__read_chk` instead of `read`, programs built
+    /// for large files call `open64` instead of `open` and older glibc versions export `__xstat`
+    /// instead of `stat`. The body is written for the function, it sees its arguments and its
+    /// `original_$name` even when a twin is called. The arguments of `open` that `__open_2` doesn't
+    /// take are zero
+    /// ```no_run
+    /// use rhook::{Hook, RunHook};
+    ///
+    /// std::process::Command::new("cat")
+    ///     .add_hooks(Hook::read(stringify!(|| Some(0))).with_twins())
+    ///     .set_hooks()
+    ///     .unwrap();
+    /// ```
+    pub fn with_twins(self) -> Vec<Hook> {
+        let twins = libcfn::TWINS
+            .iter()
+            .find(|(name, _)| *name == self.name)
+            .map_or(&[][..], |(_, twins)| twins);
+        let mut hooks = Vec::with_capacity(twins.len() + 1);
+        for twin in twins {
+            let signature = Signature::of(twin).expect("twins are libc functions");
+            hooks.push(Hook {
+                name: signature.name(),
+                signature: Cow::Borrowed(signature),
+                body: self.body.clone(),
+                kind: self.kind,
+                twin_of: Some(self.signature.clone()),
+                // the twins are newer symbols, they don't have the versions of the function
+                version: None,
+            });
+        }
+        hooks.insert(0, self);
+        hooks
+    }
+
+    /// Call the original function at this symbol version, `GLIBC_2.2.5` for example
+    ///
+    /// glibc exports several versions of some functions, like `realpath` or
+    /// `pthread_cond_wait`, and `dlsym` returns the newest one. A program linked against an older
+    /// version expects its behavior, the hook then has to forward to that version with `dlvsym`.
+    /// [Anchor::detect_versions](crate::Anchor::detect_versions) finds the versions the program
+    /// needs
+    /// ```no_run
+    /// use rhook::{Hook, RunHook};
+    ///
+    /// std::process::Command::new("./old-program")
+    ///     .add_hook(Hook::realpath(stringify!(|| None)).version("GLIBC_2.2.5"))
+    ///     .set_hooks()
+    ///     .unwrap();
+    /// ```
+    pub fn version(mut self, version: impl Into<Cow<'static, str>>) -> Hook {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// The name of the hooked function
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The signature of the hooked function
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The function of the hook library running this hook, at `layer` in the chain of hooks on
+    /// this function, see [libcfn::wrapper]
+    ///
+    /// `detected` is the version of the function the program needs, used when the hook has none,
+    /// and `edition` the edition of the hook library
+    pub(crate) fn function(
+        &self,
+        layer: usize,
+        last: bool,
+        detected: Option<&str>,
+        edition: &str,
+    ) -> String {
+        libcfn::wrapper(
+            &self.signature,
+            self.twin_of.as_deref(),
+            self.kind,
+            &self.body,
+            layer,
+            last,
+            self.version.as_deref().or(detected),
+            edition,
+        )
+    }
+
+    pub(crate) fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Whether the hooked function is exported by the C library, macros like `FD_SET` are not
+    pub(crate) fn is_hookable(&self) -> bool {
+        !UNHOOKABLE.contains(&self.name)
+    }
+
+    /// Whether the hook library calls the hooked function to find the original functions, a hook
+    /// on it would call itself
+    pub(crate) fn is_reserved(&self) -> bool {
+        RESERVED.contains(&self.name)
+    }
+}
+
+impl Signature {
+    /// The signatures of the libc functions that can be hooked, in alphabetical order
+    pub fn all() -> impl Iterator<Item = &'static Signature> {
+        SIGNATURES.iter().copied()
+    }
+}
+
+// a library exports one function per name, so hooks are the same if they hook the same function
+impl PartialEq for Hook {
+    fn eq(&self, other: &Hook) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for Hook {}
+
+impl Hash for Hook {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state)
+    }
+}
+
+impl Hook {
+    /// Hook any exported C function, from libc or from another library
+    ///
+    /// `signature` is the Rust signature of the function, with named arguments: `fn(ssl: *mut
+    /// c_void, buf: *const c_void, num: c_int) -> c_int`. The types are looked up in the same scope
+    /// as the hook body, which imports `libc::*`. `body` is a closure like the ones given to the
+    /// other hooks, and has access to the arguments and to `original_$name`
+    ///
+    /// Variadic arguments are captured with `...`: `fn(fd: c_int, cmd: c_int, ...arg: c_ulong)`
+    /// when their types are known, or a plain `...` for a list like the one of printf, see
+    /// [Param::is_variadic](crate::Param::is_variadic)
+    ///
+    /// A mistake in the signature is reported by [set_hooks](crate::Anchor::set_hooks) as a build
+    /// error of this hook
+    /// ```no_run
+    /// use rhook::{Hook, RunHook};
+    ///
+    /// std::process::Command::new("curl")
+    ///     .arg("https://example.com")
+    ///     .add_hook(Hook::custom(
+    ///         "SSL_write",
+    ///         "fn(ssl: *mut c_void, buf: *const c_void, num: c_int) -> c_int",
+    ///         stringify!(|| {
+    ///             let data = std::slice::from_raw_parts(buf as *const u8, num as usize);
+    ///             eprintln!("{}", String::from_utf8_lossy(data));
+    ///             None
+    ///         }),
+    ///     ))
+    ///     .set_hooks()
+    ///     .unwrap();
+    /// ```
+    pub fn custom(
+        name: &'static str,
+        signature: &'static str,
+        body: impl Into<Cow<'static, str>>,
+    ) -> Hook {
+        Hook {
+            name,
+            signature: Cow::Owned(Signature::parse(name, signature)),
+            body: body.into(),
+            kind: Kind::Replace,
+            twin_of: None,
+            version: None,
+        }
+    }
+}
+
+gen_hooks!(
 __errno_location
+__fgets_chk
+__fprintf_chk
+__fxstat
+__fxstat64
+__fxstatat
+__fxstatat64
+__getcwd_chk
+__libc_current_sigrtmax
+__libc_current_sigrtmin
+__lxstat
+__lxstat64
+__open64_2
+__open_2
+__openat64_2
+__openat_2
+__pread64_chk
+__pread_chk
+__printf_chk
+__read_chk
+__readlink_chk
+__realpath_chk
+__recv_chk
+__recvfrom_chk
+__snprintf_chk
+__sprintf_chk
+__xstat
+__xstat64
 _exit
 abort
 abs
@@ -84,6 +376,7 @@ accept4
 access
 acct
 addmntent
+adjtime
 adjtimex
 aio_cancel
 aio_error
@@ -93,10 +386,15 @@ aio_return
 aio_suspend
 aio_write
 alarm
-atexit
+aligned_alloc
+asctime_r
 atof
 atoi
+atol
+atoll
 backtrace
+backtrace_symbols
+backtrace_symbols_fd
 bind
 brk
 bsearch
@@ -113,6 +411,7 @@ chown
 chroot
 clearenv
 clearerr
+clock_adjtime
 clock_getcpuclockid
 clock_getres
 clock_gettime
@@ -120,29 +419,36 @@ clock_nanosleep
 clock_settime
 clone
 close
+close_range
 closedir
 closelog
+confstr
 connect
 copy_file_range
 creat
 creat64
+ctermid
+ctime_r
 daemon
 difftime
 dirfd
+dirname
 dl_iterate_phdr
 dladdr
+dladdr1
 dlclose
 dlerror
 dlinfo
 dlmopen
 dlopen
-dlsym
 dup
 dup2
 dup3
 duplocale
+eaccess
 endgrent
 endmntent
+endnetent
 endpwent
 endservent
 endspent
@@ -151,16 +457,22 @@ epoll_create
 epoll_create1
 epoll_ctl
 epoll_pwait
+epoll_pwait2
 epoll_wait
+euidaccess
 eventfd
+eventfd_read
+eventfd_write
 execl
 execle
 execlp
 execv
 execve
+execveat
 execvp
 execvpe
 exit
+explicit_bzero
 faccessat
 fallocate
 fallocate64
@@ -181,8 +493,10 @@ ferror
 fexecve
 fflush
 fgetc
+fgetgrent_r
 fgetpos
 fgetpos64
+fgetpwent_r
 fgets
 fgetspent_r
 fgetxattr
@@ -190,6 +504,7 @@ fileno
 flistxattr
 flock
 fmemopen
+fnmatch
 fopen
 fopen64
 fork
@@ -242,6 +557,7 @@ getcwd
 getdomainname
 getdtablesize
 getegid
+getentropy
 getenv
 geteuid
 getgid
@@ -253,13 +569,27 @@ getgrnam
 getgrnam_r
 getgrouplist
 getgroups
+gethostbyaddr
+gethostbyaddr_r
+gethostbyname
+gethostbyname2
+gethostbyname_r
+gethostid
 gethostname
 getifaddrs
+getitimer
 getline
 getloadavg
 getlogin
 getmntent
+getmntent_r
 getnameinfo
+getnetbyaddr
+getnetbyaddr_r
+getnetbyname
+getnetbyname_r
+getnetent
+getnetent_r
 getopt
 getpeername
 getpgid
@@ -305,8 +635,11 @@ globfree
 globfree64
 gmtime
 gmtime_r
+gnu_get_libc_release
+gnu_get_libc_version
 grantpt
 hasmntopt
+hstrerror
 iconv
 iconv_close
 iconv_open
@@ -314,6 +647,11 @@ if_freenameindex
 if_indextoname
 if_nameindex
 if_nametoindex
+inet_addr
+inet_aton
+inet_ntoa
+inet_ntop
+inet_pton
 initgroups
 inotify_add_watch
 inotify_init
@@ -337,8 +675,10 @@ isupper
 isxdigit
 kill
 killpg
+klogctl
 labs
 lchown
+lcong48
 lgetxattr
 link
 linkat
@@ -359,23 +699,27 @@ lstat
 lstat64
 lutimes
 madvise
-major
 makecontext
-makedev
 mallinfo
+mallinfo2
 malloc
+malloc_info
+malloc_stats
+malloc_trim
 malloc_usable_size
 mallopt
 memalign
+memccpy
 memchr
 memcmp
 memcpy
+memfd_create
 memmem
 memmove
+mempcpy
 memrchr
 memset
 mincore
-minor
 mkdir
 mkdirat
 mkdtemp
@@ -389,6 +733,7 @@ mkstemp
 mkstemps
 mktime
 mlock
+mlock2
 mlockall
 mmap
 mmap64
@@ -396,6 +741,7 @@ mount
 mprotect
 mq_close
 mq_getattr
+mq_notify
 mq_open
 mq_receive
 mq_send
@@ -403,6 +749,7 @@ mq_setattr
 mq_timedreceive
 mq_timedsend
 mq_unlink
+mrand48
 mremap
 msgctl
 msgget
@@ -412,6 +759,7 @@ msync
 munlock
 munlockall
 munmap
+name_to_handle_at
 nanosleep
 newlocale
 nice
@@ -421,6 +769,7 @@ ntp_adjtime
 ntp_gettime
 open
 open64
+open_by_handle_at
 open_memstream
 open_wmemstream
 openat
@@ -433,6 +782,9 @@ pause
 pclose
 perror
 personality
+pidfd_getfd
+pidfd_open
+pidfd_send_signal
 pipe
 pipe2
 poll
@@ -445,9 +797,13 @@ posix_madvise
 posix_memalign
 posix_openpt
 posix_spawn
+posix_spawn_file_actions_addchdir_np
 posix_spawn_file_actions_addclose
+posix_spawn_file_actions_addclosefrom_np
 posix_spawn_file_actions_adddup2
+posix_spawn_file_actions_addfchdir_np
 posix_spawn_file_actions_addopen
+posix_spawn_file_actions_addtcsetpgrp_np
 posix_spawn_file_actions_destroy
 posix_spawn_file_actions_init
 posix_spawnattr_destroy
@@ -472,6 +828,7 @@ pread64
 preadv
 preadv2
 preadv64
+preadv64v2
 printf
 prlimit
 prlimit64
@@ -482,11 +839,27 @@ pthread_atfork
 pthread_attr_destroy
 pthread_attr_getaffinity_np
 pthread_attr_getguardsize
+pthread_attr_getinheritsched
+pthread_attr_getschedparam
+pthread_attr_getschedpolicy
 pthread_attr_getstack
+pthread_attr_getstacksize
 pthread_attr_init
 pthread_attr_setaffinity_np
 pthread_attr_setdetachstate
+pthread_attr_setguardsize
+pthread_attr_setinheritsched
+pthread_attr_setschedparam
+pthread_attr_setschedpolicy
+pthread_attr_setstack
 pthread_attr_setstacksize
+pthread_barrier_destroy
+pthread_barrier_init
+pthread_barrier_wait
+pthread_barrierattr_destroy
+pthread_barrierattr_getpshared
+pthread_barrierattr_init
+pthread_barrierattr_setpshared
 pthread_cancel
 pthread_cond_broadcast
 pthread_cond_destroy
@@ -502,9 +875,11 @@ pthread_condattr_setclock
 pthread_condattr_setpshared
 pthread_create
 pthread_detach
+pthread_equal
 pthread_exit
 pthread_getaffinity_np
 pthread_getattr_np
+pthread_getcpuclockid
 pthread_getname_np
 pthread_getschedparam
 pthread_getspecific
@@ -512,6 +887,7 @@ pthread_join
 pthread_key_create
 pthread_key_delete
 pthread_kill
+pthread_mutex_consistent
 pthread_mutex_destroy
 pthread_mutex_init
 pthread_mutex_lock
@@ -519,10 +895,15 @@ pthread_mutex_timedlock
 pthread_mutex_trylock
 pthread_mutex_unlock
 pthread_mutexattr_destroy
+pthread_mutexattr_getprotocol
 pthread_mutexattr_getpshared
+pthread_mutexattr_getrobust
 pthread_mutexattr_init
+pthread_mutexattr_setprotocol
 pthread_mutexattr_setpshared
+pthread_mutexattr_setrobust
 pthread_mutexattr_settype
+pthread_once
 pthread_rwlock_destroy
 pthread_rwlock_init
 pthread_rwlock_rdlock
@@ -543,12 +924,22 @@ pthread_setschedparam
 pthread_setschedprio
 pthread_setspecific
 pthread_sigmask
+pthread_sigqueue
+pthread_spin_destroy
+pthread_spin_init
+pthread_spin_lock
+pthread_spin_trylock
+pthread_spin_unlock
+pthread_timedjoin_np
+pthread_tryjoin_np
 ptrace
 ptsname
 ptsname_r
 putchar
 putchar_unlocked
 putenv
+putgrent
+putpwent
 puts
 pututxline
 pwrite
@@ -556,6 +947,7 @@ pwrite64
 pwritev
 pwritev2
 pwritev64
+pwritev64v2
 qsort
 qsort_r
 quotactl
@@ -571,6 +963,7 @@ readlink
 readlinkat
 readv
 realloc
+reallocarray
 realpath
 reboot
 recv
@@ -587,7 +980,6 @@ removexattr
 rename
 renameat
 renameat2
-res_init
 rewind
 rewinddir
 rmdir
@@ -604,6 +996,7 @@ sched_setaffinity
 sched_setparam
 sched_setscheduler
 sched_yield
+seed48
 seekdir
 select
 sem_close
@@ -636,10 +1029,13 @@ setfsuid
 setgid
 setgrent
 setgroups
+sethostid
 sethostname
+setitimer
 setlocale
 setlogmask
 setmntent
+setnetent
 setns
 setpgid
 setpriority
@@ -678,6 +1074,7 @@ signal
 signalfd
 sigpending
 sigprocmask
+sigqueue
 sigsuspend
 sigtimedwait
 sigwait
@@ -697,10 +1094,13 @@ statfs64
 statvfs
 statvfs64
 statx
+stpcpy
+stpncpy
 strcasecmp
 strcasestr
 strcat
 strchr
+strchrnul
 strcmp
 strcoll
 strcpy
@@ -708,6 +1108,8 @@ strcspn
 strdup
 strerror
 strerror_r
+strftime
+strftime_l
 strlen
 strncasecmp
 strncat
@@ -716,14 +1118,19 @@ strncpy
 strndup
 strnlen
 strpbrk
+strptime
 strrchr
 strsignal
 strspn
 strstr
 strtod
+strtof
 strtok
+strtok_r
 strtol
+strtoll
 strtoul
+strtoull
 strxfrm
 swapcontext
 swapoff
@@ -732,6 +1139,7 @@ symlink
 symlinkat
 sync
 sync_file_range
+syncfs
 syscall
 sysconf
 sysctl
@@ -749,6 +1157,7 @@ tcsetattr
 tcsetpgrp
 tee
 telldir
+tgkill
 time
 timegm
 timerfd_create
@@ -793,4 +1202,121 @@ wcstombs
 wmemchr
 write
 writev
+;
+// C macros, inline functions and atexit which is linked statically: there is no symbol to
+// interpose, see Error::Unhookable
+CMSG_DATA
+CMSG_FIRSTHDR
+CMSG_LEN
+CMSG_NXTHDR
+CMSG_SPACE
+CPU_ALLOC_SIZE
+CPU_CLR
+CPU_COUNT
+CPU_COUNT_S
+CPU_EQUAL
+CPU_ISSET
+CPU_SET
+CPU_ZERO
+FD_CLR
+FD_ISSET
+FD_SET
+FD_ZERO
+IPOPT_CLASS
+IPOPT_COPIED
+IPOPT_NUMBER
+IPTOS_ECN
+IPTOS_PREC
+IPTOS_TOS
+NLA_ALIGN
+QCMD
+RT_ADDRCLASS
+RT_LOCALADDR
+RT_TOS
+SO_EE_OFFENDER
+WCOREDUMP
+WEXITSTATUS
+WIFCONTINUED
+WIFEXITED
+WIFSIGNALED
+WIFSTOPPED
+WSTOPSIG
+WTERMSIG
+W_EXITCODE
+W_STOPCODE
+atexit
+major
+makedev
+minor
+res_init
+;
+// called by the hook library to find the original functions, see Error::Reserved
+dlsym
+dlvsym
 );
+
+/// Create a [Hook] from a closure checked by the compiler of the program
+///
+/// Hooks written with `stringify!` are only compiled with the hook library, so a typo in an
+/// argument name or a wrong return type shows up at runtime. With this macro the closure is also
+/// type-checked against the libc signature when the program is compiled:
+/// - the closure lists the arguments of the libc function with their names from [libc](https://docs.rs/libc), in
+///   any order, and can end the list with `..` to skip the others
+/// - the arguments can be annotated with their types, and the closure with the return type of
+///   the libc function
+/// - the body returns an [Option] of that type like any hook, and has access to `original_$libcfn`
+///   and the imports of the hook library
+///
+/// The name can be preceded by `before` or `after` to create a [Hook::before] or [Hook::after]
+/// hook, the body then returns nothing and an after hook sees `result` and `errno`.
+///
+/// It produces the same [Hook] as `stringify!`, the body becomes the `||` closure of the hook
+/// ```no_run
+/// use rhook::{hook, RunHook};
+///
+/// std::process::Command::new("cat")
+///     .add_hook(hook!(read, |fd, buf, count: size_t| -> ssize_t {
+///         let n = original_read(fd, buf, count);
+///         eprintln!("read {} bytes from {}", n, fd);
+///         Some(n)
+///     }))
+///     .set_hooks()
+///     .unwrap();
+/// ```
+#[macro_export]
+macro_rules! hook {
+    ($($hook: tt)*) => {
+        $crate::__private::hook!($crate; $($hook)*)
+    };
+}
+
+// used by the code generated by hook!
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::libcfn;
+    pub use rhook_macros::hook;
+    pub use std::marker::PhantomData;
+
+    /// What the closure has access to in the hook library, see scaffold.rs
+    pub mod prelude {
+        pub use libc::*;
+        pub use std::ffi::CString;
+        pub use std::mem::transmute;
+        pub use std::mem::ManuallyDrop;
+
+        pub static mut COUNTER: isize = 0;
+    }
+
+    /// The argument of the libc function, see the `Args` of [libcfn]
+    pub fn arg<T>(_: fn(T)) -> T {
+        unreachable!()
+    }
+
+    /// Check that the closure returns an option of the libc function output
+    pub fn output<T>(_: PhantomData<fn() -> T>, _: Option<T>) {}
+
+    /// The value returned by the libc function, seen by after hooks
+    pub fn result<T>(_: PhantomData<fn() -> T>) -> T {
+        unreachable!()
+    }
+}
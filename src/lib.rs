@@ -25,10 +25,20 @@
 //! - closure input (which is the libc function input)
 //! - closure output (which is the libc function output)
 //! - The original function with the following name `original_$libcfn` this is useful in particular to avoid recursion
+//! - Several hooks on the same function form a chain, the first one added runs first and its `original_$libcfn` is the next hook, see [Duplicates]
+//! - To only observe or tweak a call, use [Hook::before] to rewrite the arguments or [Hook::after] to see and replace the result and `errno`
+//! - Variadic functions see their extra arguments as parameters: `mode` for `open`, `arg` for `fcntl` and `ioctl`, and `arg1`..`arg12` (integers and pointers) and `float1`..`float8` (floating point numbers) for `printf`-like functions. This relies on the x86_64 calling convention, and functions like `execl` see at most 12 extra arguments
+//! - Programs built with `_FORTIFY_SOURCE` or for large files call twins like `__read_chk` and `open64` instead of `read` and `open`, [Hook::with_twins] hooks them too with the same body
+//! - Hooks call the newest version of a function, use [Hook::version] or [Anchor::detect_versions] for programs linked against an older version of `realpath` or `pthread_cond_wait` for example
 //! - Some varaibles to make coding easier: `transmute` `ManuallyDrop` `CString` and a static mut `COUNTER`
-//! - You can find the input/output of a function by looking it up here [libc](https://docs.rs/libc)
+//! - You can find the input/output of a function by looking it up here [libc](https://docs.rs/libc), or with [Hook::signature]
 //! - Add `.map_err(|e|println("{}",e))` after `set_hooks` in order to prettify the dynamic library compiling error while debugging
 //! - If you take ownership of an input value inside of the closure, be sure to use ManuallyDrop so you don't free it
+//! - The hook libraries are built in `$RHOOK_DIR`, or in `rhookdyl-$UID` inside `$TMPDIR`, use [workspace](Anchor::workspace) to choose another directory
+//! - To avoid compiling at runtime, compile the hooks from a build script with the `rhook-build` crate and embed them with [embed]
+//! - Write hooks with [hook!] instead of `stringify!` to have the compiler of your program check the closure arguments and return type
+//! - Functions that are not in [Hook::names], from libc or any other library, can be hooked with [Hook::custom] and their signature
+//! - Hooks read from a config file can be created with [Hook::from_name], the body can be a `String`, [Hook::names] lists the functions that can be hooked
 //!
 //!## Example
 //!
@@ -63,23 +73,60 @@
 #[cfg(not(unix))]
 compile_error!("This crate is unix only");
 
-pub(crate) mod libcfn;
+pub mod cache;
+mod cargo;
+mod ctypes;
+mod elf;
+mod error;
+#[doc(hidden)]
+pub mod libcfn;
+mod lock;
+mod options;
+mod rustc;
+mod vendor;
+mod workspace;
 use std::{
-    collections::HashSet,
-    io::{self, Write},
-    sync::Mutex,
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
-use io::Read;
+use error::SourceMap;
+pub use error::{BuildError, Diagnostic, Error, UnknownFunction};
+use lock::WorkspaceLock;
 use once_cell::sync::Lazy;
-use std::io::Result;
-use std::process::{Command, Stdio};
+pub use options::{Backend, Duplicates, Profile};
+use options::Options;
+use std::process::Command;
 
-// synchronize dynamic library building between different threads
-static RHOOK_DYNLIB_DIR_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+/// The result type of this crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+// synchronize dynamic library building between different threads, one lock per hook set
+static RHOOK_DYNLIB_DIR_LOCKS: Lazy<Mutex<HashMap<String, Arc<Mutex<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn dynlib_lock(key: &str) -> Arc<Mutex<()>> {
+    RHOOK_DYNLIB_DIR_LOCKS
+        .lock()
+        .expect("should not happen")
+        .entry(key.to_string())
+        .or_default()
+        .clone()
+}
 
 mod hook;
+#[doc(hidden)]
+pub use hook::__private;
 pub use hook::Hook;
+mod library;
+pub use library::HookLibrary;
+mod signature;
+pub use signature::{Param, Signature};
 
 /// The struct that holds the current command hooks
 ///
@@ -88,36 +135,161 @@ pub use hook::Hook;
 /// This struct guarantees by the type system that [Anchor::set_hooks] is called
 pub struct Anchor<'a> {
     command: Option<&'a mut Command>,
-    hooks: Option<HashSet<Hook>>,
+    /// in the order they were added, it is the order of the chains of hooks on one function
+    hooks: Option<Vec<Hook>>,
+    options: Options,
 }
 
 impl<'a> Anchor<'a> {
     /// Set the hooks, this is a required method since it does the actual work of creating a
     /// dynamic library and linking the target program with it
+    ///
+    /// Libraries are cached by the hash of their source, so setting the same hooks again reuses the
+    /// library that was already built
+    ///
+    /// If a hook fails to compile the returned [Error::Build] lists the compiler messages, each
+    /// one tied to its hook and to the line inside the hook closure
     pub fn set_hooks(&mut self) -> Result<&mut Command> {
-        //only one Command should do the next lines at a given time
-        //take lock here
-        let _lock = RHOOK_DYNLIB_DIR_LOCK.lock().expect("should not happen");
+        let library = self.build()?;
 
-        prepare()?;
+        Ok(library.attach(
+            self.command
+                .take()
+                .expect(Self::FIELDS_ARE_ALWAYS_NOT_NONE_JUSTIFICATION),
+        ))
+    }
 
-        for hook in self
-            .hooks
-            .as_ref()
-            .expect(Self::FIELDS_ARE_ALWAYS_NOT_NONE_JUSTIFICATION)
-        {
-            append(hook.function())?;
-        }
-        build_dylib()?;
+    /// Build the hook library without touching the command
+    ///
+    /// The returned library lives in the workspace cache, it can be attached to other commands
+    /// with [HookLibrary::attach]
+    pub fn build(&self) -> Result<HookLibrary> {
+        let (library, warnings) = self.build_cached()?;
+        Ok(HookLibrary::new(library).with_warnings(warnings))
+    }
 
-        //drop lock here
-        drop(_lock);
+    /// Build the hook library and copy it to `path`
+    ///
+    /// This is useful to build the hooks once, as a release step for example, and ship the library.
+    /// It can then be used with [HookLibrary::open] without any toolchain installed
+    pub fn build_to(&self, path: impl AsRef<Path>) -> Result<HookLibrary> {
+        let path = path.as_ref();
+        let (library, warnings) = self.build_cached()?;
 
-        Ok(self
-            .command
-            .take()
-            .expect(Self::FIELDS_ARE_ALWAYS_NOT_NONE_JUSTIFICATION)
-            .env("LD_PRELOAD", "/tmp/rhookdyl/target/debug/librhookdyl.so"))
+        // a program loading the library never sees a half written file
+        write_atomic(path, |tmp| std::fs::copy(&library, tmp).map(drop))?;
+        Ok(HookLibrary::open(path)?.with_warnings(warnings))
+    }
+
+    /// Type-check the hooks without building the library nor touching the command
+    ///
+    /// It returns the warnings if the hooks compile, and an [Error::Build] with the errors
+    /// otherwise, this is useful to validate hooks in CI
+    pub fn check_hooks(&self) -> Result<Vec<Diagnostic>> {
+        self.check_hooks_list()?;
+        let generated = self.generate();
+        let workspace = workspace::resolve(self.options.workspace.as_deref());
+        workspace::create(&workspace)?;
+
+        locked(&workspace, &generated.key, || {
+            let dir = generated.write(&workspace, &self.options)?;
+            match self.options.backend {
+                Backend::Cargo => cargo::check_dylib(
+                    &workspace,
+                    &dir,
+                    &generated.key,
+                    &self.options,
+                    &generated.source_map,
+                ),
+                Backend::Rustc => rustc::check_dylib(
+                    &dir,
+                    &generated.key,
+                    &self.options,
+                    &generated.source_map,
+                ),
+            }
+        })
+    }
+
+    /// The full rust source of the hook library, as it is written before being compiled
+    pub fn generated_source(&self) -> String {
+        let generated = self.generate();
+        let mut source = SCAFFOLD.to_string();
+        source.extend(generated.functions);
+        source += &generated.libc_module;
+        source
+    }
+
+    /// Build the hook library inside `dir` instead of the default workspace
+    ///
+    /// By default the workspace is `$RHOOK_DIR` if it is set, and `rhookdyl-$UID` inside the
+    /// temporary directory (`$TMPDIR` or `/tmp`) otherwise.
+    ///
+    /// The workspace is created with `0700` permissions, and [set_hooks](Anchor::set_hooks)
+    /// refuses to build in it if it is owned by another user or writable by group or others
+    pub fn workspace(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.options.workspace = Some(dir.into());
+        self
+    }
+
+    /// Choose the tool used to compile the hook library, see [Backend]
+    pub fn backend(&mut self, backend: Backend) -> &mut Self {
+        self.options.backend = backend;
+        self
+    }
+
+    /// Choose the profile the hook library is built with, the default is [Profile::Debug]
+    pub fn profile(&mut self, profile: Profile) -> &mut Self {
+        self.options.profile = profile;
+        self
+    }
+
+    /// Override the opt-level of the selected profile (`0` to `3`, `s` or `z`)
+    pub fn opt_level(&mut self, opt_level: &str) -> &mut Self {
+        self.options.opt_level = Some(opt_level.to_string());
+        self
+    }
+
+    /// Pass extra flags to rustc, like `RUSTFLAGS` does
+    pub fn rustflags<I, S>(&mut self, flags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .rustflags
+            .extend(flags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Build with the given rustup toolchain, for example `nightly` or `1.52.0`
+    pub fn toolchain(&mut self, toolchain: &str) -> &mut Self {
+        self.options.toolchain = Some(toolchain.to_string());
+        self
+    }
+
+    /// Choose the edition the hooks are compiled with, the default is `2018`
+    pub fn edition(&mut self, edition: &str) -> &mut Self {
+        self.options.edition = Some(edition.to_string());
+        self
+    }
+
+    /// Choose what happens when several hooks are added on the same function, the default is
+    /// to chain them, see [Duplicates]
+    pub fn duplicates(&mut self, duplicates: Duplicates) -> &mut Self {
+        self.options.duplicates = duplicates;
+        self
+    }
+
+    /// Forward every hook to the version of the function the program is linked against
+    ///
+    /// The program of the command is read to find the symbol versions it needs, the hooks
+    /// without a [version](Hook::version) then call the original function with `dlvsym` at that
+    /// version instead of the newest one. Nothing changes for programs that are not ELF files,
+    /// like scripts, and for hook libraries built without a command
+    pub fn detect_versions(&mut self) -> &mut Self {
+        self.options.detect_versions = true;
+        self
     }
 
     //-----------------
@@ -127,15 +299,156 @@ impl<'a> Anchor<'a> {
     fn new(command: &'a mut Command) -> Self {
         Self {
             command: Some(command),
-            hooks: Some(HashSet::new()),
+            hooks: Some(Vec::new()),
+            options: Options::default(),
+        }
+    }
+
+    /// Move the content of this Anchor into a new one, used to chain add_hook/add_hooks calls
+    fn take(&mut self) -> Self {
+        Self {
+            command: self.command.take(),
+            hooks: self.hooks.take(),
+            options: std::mem::take(&mut self.options),
         }
     }
 
+    /// Build the hook library if it is not in the cache yet, return its path and the warnings of
+    /// the build (none when it was already in the cache)
+    fn build_cached(&self) -> Result<(PathBuf, Vec<Diagnostic>)> {
+        self.check_hooks_list()?;
+        let generated = self.generate();
+        let options = &self.options;
+        let key = &generated.key;
+
+        let workspace = workspace::resolve(options.workspace.as_deref());
+        workspace::create(&workspace)?;
+
+        // every hook set gets its own crate and its own library, so a Command always loads the
+        // hooks it was given even if another thread builds a different set in the meantime
+        let library = cache::library_path(&workspace, key);
+        if library.exists() {
+            return Ok((library, Vec::new()));
+        }
+
+        locked(&workspace, key, || {
+            // another thread or process might have built the same library while we were waiting
+            // for the lock
+            let mut warnings = Vec::new();
+            if !library.exists() {
+                let dir = generated.write(&workspace, options)?;
+                let (built, diagnostics) = match options.backend {
+                    Backend::Cargo => {
+                        cargo::build_dylib(&workspace, &dir, key, options, &generated.source_map)?
+                    }
+                    Backend::Rustc => {
+                        rustc::build_dylib(&dir, key, options, &generated.source_map)?
+                    }
+                };
+                cache::store(&workspace, &built, key, &generated.hooks)?;
+                warnings = diagnostics;
+            }
+            Ok((library, warnings))
+        })
+    }
+
+    /// Generate the code of the hook library and its cache key
+    ///
+    /// The hooks are sorted by function name so the same hooks always produce the same library
+    /// source, the sort is stable so hooks on the same function keep their chain order
+    fn generate(&self) -> Generated {
+        let mut hooks: Vec<&Hook> = self
+            .hooks
+            .as_ref()
+            .expect(Self::FIELDS_ARE_ALWAYS_NOT_NONE_JUSTIFICATION)
+            .iter()
+            .collect();
+        hooks.sort_by_key(|hook| hook.name());
+
+        let versions = match &self.command {
+            Some(command) if self.options.detect_versions => {
+                elf::find_program(Path::new(command.get_program()))
+                    .map(|program| elf::needed_versions(&program))
+                    .unwrap_or_default()
+            }
+            _ => HashMap::new(),
+        };
+
+        let mut source = SCAFFOLD.to_string();
+        let mut source_map = SourceMap::default();
+        let mut functions = Vec::new();
+        for chain in hooks.chunk_by(|a, b| a.name() == b.name()) {
+            for (layer, hook) in chain.iter().enumerate() {
+                let version = versions.get(hook.name()).map(String::as_str);
+                let function = hook.function(
+                    layer,
+                    layer + 1 == chain.len(),
+                    version,
+                    self.options.edition(),
+                );
+                source_map.push(&source, hook.name(), hook.body(), &function);
+                source += &function;
+                functions.push(function);
+            }
+        }
+        hooks.dedup_by_key(|hook| hook.name());
+
+        let cargo_toml = cargo::cargo_toml(&self.options);
+        let options_key = self.options.key();
+        // the rustc backend replaces the libc crate with a module at the end of the source
+        let libc_module = match self.options.backend {
+            Backend::Cargo => String::new(),
+            Backend::Rustc => ctypes::libc_module(self.options.edition()),
+        };
+        let mut key_parts = vec![options_key.as_str()];
+        if self.options.backend == Backend::Cargo {
+            key_parts.push(&cargo_toml);
+        }
+        key_parts.push(&libc_module);
+        key_parts.push(SCAFFOLD);
+        key_parts.extend(functions.iter().map(String::as_str));
+        let key = cache::key(&key_parts);
+
+        Generated {
+            hooks: hooks.iter().map(|hook| hook.name()).collect(),
+            functions,
+            source_map,
+            cargo_toml,
+            libc_module,
+            key,
+        }
+    }
+
+    /// Fail if a hook can never run, or if a function is hooked more than once and duplicates
+    /// are rejected
+    fn check_hooks_list(&self) -> Result<()> {
+        let hooks = self
+            .hooks
+            .as_ref()
+            .expect(Self::FIELDS_ARE_ALWAYS_NOT_NONE_JUSTIFICATION);
+        if let Some(hook) = hooks.iter().find(|hook| !hook.is_hookable()) {
+            return Err(Error::Unhookable(hook.name().to_string()));
+        }
+        if let Some(hook) = hooks.iter().find(|hook| hook.is_reserved()) {
+            return Err(Error::Reserved(hook.name().to_string()));
+        }
+        if self.options.duplicates == Duplicates::Chain {
+            return Ok(());
+        }
+        let mut names = HashSet::new();
+        for hook in hooks {
+            if !names.insert(hook.name()) {
+                return Err(Error::DuplicateHook(hook.name().to_string()));
+            }
+        }
+        Ok(())
+    }
+
     fn insert_hook(&mut self, hook: Hook) {
         self.hooks
             .as_mut()
             .expect(Self::FIELDS_ARE_ALWAYS_NOT_NONE_JUSTIFICATION)
-            .insert(hook);
+            .push(hook);
     }
 
     fn insert_hooks(&mut self, hooks: Vec<Hook>) {
@@ -148,98 +461,118 @@ impl<'a> Anchor<'a> {
 /// Specify libc hooks for a Command
 pub trait RunHook {
     /// Add a libc hook to the command
-    fn add_hook(&mut self, hook: Hook) -> Anchor;
+    fn add_hook(&mut self, hook: Hook) -> Anchor<'_>;
     /// Add a Vec of libc hooks to the command
-    fn add_hooks(&mut self, hooks: Vec<Hook>) -> Anchor;
+    fn add_hooks(&mut self, hooks: Vec<Hook>) -> Anchor<'_>;
 }
 
 impl RunHook for Anchor<'_> {
-    fn add_hook(&mut self, hook: Hook) -> Anchor {
+    fn add_hook(&mut self, hook: Hook) -> Anchor<'_> {
         self.insert_hook(hook);
-        Self {
-            command: self.command.take(),
-            hooks: self.hooks.take(),
-        }
+        self.take()
     }
 
-    fn add_hooks(&mut self, hooks: Vec<Hook>) -> Anchor {
+    fn add_hooks(&mut self, hooks: Vec<Hook>) -> Anchor<'_> {
         self.insert_hooks(hooks);
-        Self {
-            command: self.command.take(),
-            hooks: self.hooks.take(),
-        }
+        self.take()
     }
 }
 
 impl RunHook for Command {
-    fn add_hook(&mut self, hook: Hook) -> Anchor {
+    fn add_hook(&mut self, hook: Hook) -> Anchor<'_> {
         let mut anchor = Anchor::new(self);
         anchor.insert_hook(hook);
         anchor
     }
-    fn add_hooks(&mut self, hooks: Vec<Hook>) -> Anchor {
+    fn add_hooks(&mut self, hooks: Vec<Hook>) -> Anchor<'_> {
         let mut anchor = Anchor::new(self);
         anchor.insert_hooks(hooks);
         anchor
     }
 }
 
-/// Create the dynamic library and write the scaffold to it
-fn prepare() -> Result<()> {
-    const CARGO_TOML: &str = r#"[package]
-name = "rhookdyl"
-version = "0.1.0"
-edition = "2018"
-[lib]
-crate-type = ["dylib"]
-[dependencies]
-libc = "0.2.92""#;
-
-    // Ignore project already exists error
-    Command::new("cargo")
-        .arg("new")
-        .arg("rhookdyl")
-        .arg("--lib")
-        .current_dir("/tmp")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?
-        .wait()?;
-    std::fs::write("/tmp/rhookdyl/Cargo.toml", CARGO_TOML)?;
-    std::fs::write("/tmp/rhookdyl/src/lib.rs", include_str!("scaffold.rs"))?;
-    Ok(())
+/// The generated hook library of an Anchor, before it is written to the workspace
+struct Generated {
+    /// The names of the hooked functions
+    hooks: Vec<&'static str>,
+    functions: Vec<String>,
+    source_map: SourceMap,
+    cargo_toml: String,
+    /// The `libc` module of the rustc backend, empty with cargo, see [ctypes::libc_module]
+    libc_module: String,
+    /// The cache key of the library, see [cache::key]
+    key: String,
+}
+
+impl Generated {
+    /// Write the library source to its directory in the workspace, return that directory
+    fn write(&self, workspace: &Path, options: &Options) -> std::io::Result<PathBuf> {
+        let dir = cache::entry_dir(workspace, &self.key);
+        let source = match options.backend {
+            Backend::Cargo => cargo::prepare(&dir, &self.cargo_toml, &self.key)?,
+            Backend::Rustc => rustc::prepare(&dir)?,
+        };
+        for function in &self.functions {
+            append(&source, function)?;
+        }
+        append(&source, &self.libc_module)?;
+        Ok(dir)
+    }
+}
+
+/// Run `f` while holding the locks of the hook set `key`
+fn locked<T>(workspace: &Path, key: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    //only one Command should build a given hook set at a given time
+    //take lock here
+    let lock = dynlib_lock(key);
+    let _lock = lock.lock().expect("should not happen");
+    // and only one process should touch the workspace at a given time
+    let _workspace_lock = WorkspaceLock::acquire(workspace)?;
+
+    f()
+}
+
+const SCAFFOLD: &str = include_str!("scaffold.rs");
+
+/// Name of the crate of a hook set, it is unique so all the hook sets can share one target
+/// directory (and build libc only once) without overwriting each other's library
+fn crate_name(key: &str) -> String {
+    format!("rhookdyl_{}", key)
 }
 
 /// Append rust generated code to the initial scaffold
-fn append(fun: String) -> Result<()> {
+fn append(source: &Path, fun: &str) -> std::io::Result<()> {
     std::fs::OpenOptions::new()
         .append(true)
-        .open("/tmp/rhookdyl/src/lib.rs")?
+        .open(source)?
         .write_all(fun.as_bytes())?;
     Ok(())
 }
 
-/// Build the dynamic library
-fn build_dylib() -> Result<()> {
-    let mut stderr = String::new();
-    let mut process = Command::new("cargo")
-        .arg("b")
-        .args(&["--color", "always"])
-        .current_dir("/tmp/rhookdyl")
-        .stderr(Stdio::piped())
-        .env("CARGO_TARGET_DIR", "/tmp/rhookdyl/target")
-        .spawn()?;
-    process
-        .stderr
-        .as_mut()
-        .expect("stderr is piped")
-        .read_to_string(&mut stderr)?;
-
-    let status = process.wait()?;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(io::Error::new(io::ErrorKind::Other, stderr))
-    }
+/// Write a file with `write` under a temporary name then rename it to `path`, so a reader never
+/// sees a half written file
+///
+/// The temporary name is unique to the call, so several threads can write the same path at once.
+/// When the rename fails but `path` exists, another call wrote it in the meantime and its file is
+/// kept
+fn write_atomic<E: From<std::io::Error>>(
+    path: &Path,
+    write: impl FnOnce(&Path) -> std::result::Result<(), E>,
+) -> std::result::Result<(), E> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = PathBuf::from(tmp);
+
+    let written = write(&tmp).and_then(|()| match std::fs::rename(&tmp, path) {
+        Err(_) if path.exists() => Ok(()),
+        renamed => Ok(renamed?),
+    });
+    // the temporary file is gone once renamed, otherwise clean it up
+    let _ = std::fs::remove_file(&tmp);
+    written
 }
@@ -1,25 +1,316 @@
 #![allow(non_snake_case)]
 
-macro_rules! libc {
-(fn $name: ident($($arg: ident: $typee: ty$(,)?)*) -> $ret_type: ty) => (
-    pub(crate) fn $name(fun: &str) -> String {
-        format!("{function_type}
-            {{
-            let original_{function_name} = dlsym(RTLD_NEXT, \"{function_name}\0\".as_ptr() as _);
-            #[allow(unused_variables)]
-            let original_{function_name}: {function_type_without_vars} = transmute(original_{function_name});
-            if let Some(result) = ({user_closure})() {{
+use crate::hook::Kind;
+use crate::signature::{Param, Signature};
+use std::borrow::Cow;
+
+/// The function exported by the hook library in place of the function of `signature`
+///
+/// It runs the hook closure and calls the original function when the closure returns `None`, or
+/// around the original function for [Kind::Before] and [Kind::After] hooks.
+/// Several hooks on one function form a chain: `layer` is the position of this hook in it, the
+/// first layer is the exported function and the original of every layer is the next one. Only
+/// the last layer calls the real function, found once with `dlsym`, or with `dlvsym` at `version`.
+/// `edition` is the edition the hook library is compiled with
+///
+/// A hook on a twin of a function, see [TWINS], has a body written for `twin_of`: it sees the
+/// arguments of `twin_of` and an `original_$twin_of` that calls the twin
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn wrapper(
+    signature: &Signature,
+    twin_of: Option<&Signature>,
+    kind: Kind,
+    fun: &str,
+    layer: usize,
+    last: bool,
+    version: Option<&str>,
+    edition: &str,
+) -> String {
+    let params = signature.params();
+    // a before hook rewrites the arguments
+    let mutable = if kind == Kind::Before { "mut " } else { "" };
+    let function_args: String = params
+        .iter()
+        .map(|param| format!("{}{}: {}, ", mutable, param.name(), param.ty()))
+        .collect();
+    let function_types: String = params
+        .iter()
+        .map(|param| format!("{}, ", param.ty()))
+        .collect();
+    let hooked = twin_of.unwrap_or(signature);
+    let function_vars: String = hooked
+        .params()
+        .iter()
+        .map(|param| format!("{}, ", param.name()))
+        .collect();
+    // the libc function is called as a variadic function, that's what sets the number of vector
+    // registers used by the arguments
+    let original_types: String = if last && params.iter().any(Param::is_variadic) {
+        params
+            .iter()
+            .filter(|param| !param.is_variadic())
+            .map(|param| format!("{}, ", param.ty()))
+            .chain(Some("...".to_string()))
+            .collect()
+    } else {
+        function_types.clone()
+    };
+    let name = signature.name();
+    let function_type = if layer == 0 {
+        let no_mangle = if crate::options::unsafe_attributes(edition) {
+            "#[unsafe(no_mangle)]"
+        } else {
+            "#[no_mangle]"
+        };
+        format!("{} pub unsafe extern \"C\" fn {}", no_mangle, name)
+    } else {
+        format!("unsafe extern \"C\" fn {}", layer_name(name, layer))
+    };
+    // the last layer finds the original once and keeps it in a static, see rhook_original in
+    // scaffold.rs
+    let original = if last {
+        let version = match version {
+            Some(version) => format!("Some(\"{}\\0\")", version),
+            None => "None".to_string(),
+        };
+        let fallback = match LIBC_ALIASES.iter().find(|(function, _)| *function == name) {
+            Some((_, alias)) => format!(
+                "{{
+                    {}extern \"C\" {{ fn {alias}(); }}
+                    {alias} as *mut c_void
+                }}",
+                if crate::options::unsafe_attributes(edition) { "unsafe " } else { "" },
+                alias = alias
+            ),
+            None => "std::ptr::null_mut()".to_string(),
+        };
+        format!(
+            "{{
+                static ORIGINAL: std::sync::atomic::AtomicPtr<c_void> = std::sync::atomic::AtomicPtr::new(std::ptr::null_mut());
+                rhook_original(&ORIGINAL, \"{}\\0\", {}, {})
+            }}",
+            name, version, fallback
+        )
+    } else {
+        format!(
+            "{} as unsafe extern \"C\" fn({}) -> {}",
+            layer_name(name, layer + 1),
+            function_types,
+            signature.ret()
+        )
+    };
+    let twin = match twin_of {
+        Some(twin_of) => twin(signature, twin_of, mutable),
+        None => String::new(),
+    };
+    let call = match kind {
+        Kind::Replace => format!(
+            "if let Some(result) = ({user_closure})() {{
                 result
             }} else {{
                 original_{function_name}({function_vars})
-            }}
+            }}",
+            user_closure = fun,
+            function_name = hooked.name(),
+            function_vars = function_vars
+        ),
+        Kind::Before => format!(
+            "({user_closure})();
+            original_{function_name}({function_vars})",
+            user_closure = fun,
+            function_name = hooked.name(),
+            function_vars = function_vars
+        ),
+        // errno is read right after the call, before anything in the hook can change it
+        Kind::After => format!(
+            "#[allow(unused_mut)]
+            let mut result = original_{function_name}({function_vars});
+            #[allow(unused_mut)]
+            let mut errno = *libc::__errno_location();
+            ({user_closure})();
+            *libc::__errno_location() = errno;
+            result",
+            user_closure = fun,
+            function_name = hooked.name(),
+            function_vars = function_vars
+        ),
+    };
+    format!("#[allow(unused_mut)]
+            {function_type}({function_args}) -> {ret}
+            {{
+            let original_{function_name} = {original};
+            #[allow(unused_variables)]
+            let original_{function_name}: extern \"C\" fn({original_types}) -> {ret} = transmute(original_{function_name});
+            {twin}
+            {call}
             }}
             "
-        ,function_type = stringify!(#[no_mangle] pub unsafe extern "C" fn $name ($($arg: $typee,)*) -> $ret_type).to_string()
-        ,function_type_without_vars = stringify!(extern "C" fn($($typee,)*) -> $ret_type)
-        ,function_vars = stringify!($($arg,)*)
-        ,function_name = stringify!($name)
-        ,user_closure = fun)})
+    ,function_type = function_type
+    ,function_name = name
+    ,function_args = function_args
+    ,original_types = original_types
+    ,original = original
+    ,twin = twin
+    ,ret = signature.ret()
+    ,call = call)
+}
+
+/// Give the body of a hook on the twin of `signature` the arguments and original function of
+/// `twin_of`
+///
+/// The parameters are matched by name, the ones `twin_of` doesn't have like the `buflen` of
+/// `__read_chk` are passed as they were received and the ones the twin doesn't have like the
+/// `mode` of `__open_2` are zero
+fn twin(signature: &Signature, twin_of: &Signature, mutable: &str) -> String {
+    let find = |params: &[Param], name: &str| {
+        params
+            .iter()
+            .find(|param| param.name() == name)
+            .cloned()
+    };
+    let mut twin = String::new();
+    for param in twin_of.params() {
+        let value = match find(signature.params(), param.name()) {
+            Some(own) if own.ty() == param.ty() => continue,
+            // stat and stat64 are the same struct on x86_64
+            Some(_) => format!("{} as _", param.name()),
+            None => "std::mem::zeroed()".to_string(),
+        };
+        twin += &format!(
+            "#[allow(unused_variables)]
+            let {}{}: {} = {};
+            ",
+            mutable,
+            param.name(),
+            param.ty(),
+            value
+        );
+    }
+    let closure_args: String = twin_of
+        .params()
+        .iter()
+        .map(|param| format!("{}: {}, ", param.name(), param.ty()))
+        .collect();
+    let twin_vars: String = signature
+        .params()
+        .iter()
+        .map(|param| match find(twin_of.params(), param.name()) {
+            Some(hooked) if hooked.ty() != param.ty() => format!("{} as _, ", param.name()),
+            _ => format!("{}, ", param.name()),
+        })
+        .collect();
+    twin + &format!(
+        "#[allow(unused_variables)]
+        let original_{twin_of} = move |{closure_args}| original_{name}({twin_vars});",
+        twin_of = twin_of.name(),
+        closure_args = closure_args,
+        name = signature.name(),
+        twin_vars = twin_vars
+    )
+}
+
+/// The twins of a function: its large file variant, the variant checking the size of the buffer
+/// that `_FORTIFY_SOURCE` calls instead of it, and the one older glibc versions export in its place
+///
+/// Programs call the twins without knowing it, [Hook::with_twins](crate::Hook::with_twins) hooks
+/// them with the body of the function. The parameters of a twin have the names of the ones of
+/// the function, see [twin]
+pub(crate) static TWINS: &[(&str, &[&str])] = &[
+    ("creat", &["creat64"]),
+    ("fgets", &["__fgets_chk"]),
+    ("fopen", &["fopen64"]),
+    ("fprintf", &["__fprintf_chk"]),
+    ("fstat", &["fstat64", "__fxstat", "__fxstat64"]),
+    ("fstatat", &["fstatat64", "__fxstatat", "__fxstatat64"]),
+    ("getcwd", &["__getcwd_chk"]),
+    ("lstat", &["lstat64", "__lxstat", "__lxstat64"]),
+    ("open", &["open64", "__open_2", "__open64_2"]),
+    ("openat", &["openat64", "__openat_2", "__openat64_2"]),
+    ("pread", &["pread64", "__pread_chk", "__pread64_chk"]),
+    ("printf", &["__printf_chk"]),
+    ("read", &["__read_chk"]),
+    ("readlink", &["__readlink_chk"]),
+    ("realpath", &["__realpath_chk"]),
+    ("recv", &["__recv_chk"]),
+    ("recvfrom", &["__recvfrom_chk"]),
+    ("snprintf", &["__snprintf_chk"]),
+    ("sprintf", &["__sprintf_chk"]),
+    ("stat", &["stat64", "__xstat", "__xstat64"]),
+];
+
+/// The functions libc also exports under another name, called by a hook on them while `dlsym`
+/// looks up their original
+///
+/// glibc before 2.34 allocates with `calloc` in `dlsym`, so a hook on the allocation functions
+/// runs before its original is known, see `rhook_original` in scaffold.rs
+static LIBC_ALIASES: &[(&str, &str)] = &[
+    ("calloc", "__libc_calloc"),
+    ("free", "__libc_free"),
+    ("malloc", "__libc_malloc"),
+    ("memalign", "__libc_memalign"),
+    ("realloc", "__libc_realloc"),
+];
+
+/// The name of a hook in a chain after the first one
+fn layer_name(name: &str, layer: usize) -> String {
+    format!("rhook_{}_{}", name, layer)
+}
+
+// C variadic functions can't be defined in stable Rust, so the exported function takes fixed extra
+// parameters instead. On x86_64 they are passed in the same registers and stack slots as the
+// variadic arguments, and the original function is called as a variadic function with them
+macro_rules! libc {
+(@ $name: ident [$($arg: ident: $typee: ty,)*] [$($var_arg: ident: $var_type: ty,)*] $ret_type: ty) => (
+    /// The signature of the libc function, and its inputs used by [hook!](crate::hook) to check
+    /// hooks with the host compiler
+    pub mod $name {
+        #[allow(unused_imports)]
+        use libc::*;
+
+        pub(crate) static SIGNATURE: super::Signature = super::Signature {
+            name: super::Cow::Borrowed(stringify!($name)),
+            params: super::Cow::Borrowed(&[$(super::Param {
+                name: super::Cow::Borrowed(stringify!($arg)),
+                ty: super::Cow::Borrowed(stringify!($typee)),
+                variadic: false,
+            },)* $(super::Param {
+                name: super::Cow::Borrowed(stringify!($var_arg)),
+                ty: super::Cow::Borrowed(stringify!($var_type)),
+                variadic: true,
+            },)*]),
+            ret: super::Cow::Borrowed(stringify!($ret_type)),
+        };
+
+        // a field can't hold an elided reference but a function pointer can, the argument type is
+        // taken back from it by `__private::arg`
+        pub struct Args {
+            $(pub $arg: fn($typee),)*
+            $(pub $var_arg: fn($var_type),)*
+        }
+
+        impl Args {
+            pub fn original(&self) -> extern "C" fn($($typee,)* $($var_type,)*) -> $ret_type {
+                unreachable!()
+            }
+            pub fn output(&self) -> std::marker::PhantomData<fn() -> $ret_type> {
+                std::marker::PhantomData
+            }
+        }
+    }
+    );
+// variadic arguments with known types, like the mode of open
+(fn $name: ident($($arg: ident: $typee: ty,)* ...$($var_arg: ident: $var_type: ty),+) -> $ret_type: ty) => (
+    libc!(@ $name [$($arg: $typee,)*] [$($var_arg: $var_type,)+] $ret_type);
+);
+// a list of arguments like the ones of printf, the integers and pointers are passed in the general
+// purpose registers then on the stack and the floating point numbers in the vector registers, see
+// signature::VARIADIC_ARGS
+(fn $name: ident($($arg: ident: $typee: ty,)* ...) -> $ret_type: ty) => (
+    libc!(@ $name [$($arg: $typee,)*] [arg1: c_long, arg2: c_long, arg3: c_long, arg4: c_long, arg5: c_long, arg6: c_long, arg7: c_long, arg8: c_long, arg9: c_long, arg10: c_long, arg11: c_long, arg12: c_long, float1: c_double, float2: c_double, float3: c_double, float4: c_double, float5: c_double, float6: c_double, float7: c_double, float8: c_double,] $ret_type);
+);
+(fn $name: ident($($arg: ident: $typee: ty$(,)?)*) -> $ret_type: ty) => (
+    libc!(@ $name [$($arg: $typee,)*] [] $ret_type);
+);
 }
 
 // libc functions starts here
@@ -63,6 +354,33 @@ libc!(fn WTERMSIG(status: c_int) -> c_int);
 libc!(fn W_EXITCODE(ret: c_int, sig: c_int) -> c_int);
 libc!(fn W_STOPCODE(sig: c_int) -> c_int);
 libc!(fn __errno_location() -> *mut c_int);
+libc!(fn __fgets_chk(buf: *mut c_char, buflen: size_t, n: c_int, stream: *mut FILE) -> *mut c_char);
+libc!(fn __fprintf_chk(stream: *mut FILE, flag: c_int, format: *const c_char, ...) -> c_int);
+libc!(fn __fxstat(ver: c_int, fildes: c_int, buf: *mut stat) -> c_int);
+libc!(fn __fxstat64(ver: c_int, fildes: c_int, buf: *mut stat64) -> c_int);
+libc!(fn __fxstatat(ver: c_int, dirfd: c_int, pathname: *const c_char, buf: *mut stat, flags: c_int) -> c_int);
+libc!(fn __fxstatat64(ver: c_int, dirfd: c_int, pathname: *const c_char, buf: *mut stat64, flags: c_int) -> c_int);
+libc!(fn __getcwd_chk(buf: *mut c_char, size: size_t, buflen: size_t) -> *mut c_char);
+libc!(fn __libc_current_sigrtmax() -> c_int);
+libc!(fn __libc_current_sigrtmin() -> c_int);
+libc!(fn __lxstat(ver: c_int, path: *const c_char, buf: *mut stat) -> c_int);
+libc!(fn __lxstat64(ver: c_int, path: *const c_char, buf: *mut stat64) -> c_int);
+libc!(fn __open64_2(path: *const c_char, oflag: c_int) -> c_int);
+libc!(fn __open_2(path: *const c_char, oflag: c_int) -> c_int);
+libc!(fn __openat64_2(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int);
+libc!(fn __openat_2(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int);
+libc!(fn __pread64_chk(fd: c_int, buf: *mut c_void, count: size_t, offset: off64_t, buflen: size_t) -> ssize_t);
+libc!(fn __pread_chk(fd: c_int, buf: *mut c_void, count: size_t, offset: off_t, buflen: size_t) -> ssize_t);
+libc!(fn __printf_chk(flag: c_int, format: *const c_char, ...) -> c_int);
+libc!(fn __read_chk(fd: c_int, buf: *mut c_void, count: size_t, buflen: size_t) -> ssize_t);
+libc!(fn __readlink_chk(path: *const c_char, buf: *mut c_char, bufsz: size_t, buflen: size_t) -> ssize_t);
+libc!(fn __realpath_chk(pathname: *const c_char, resolved: *mut c_char, resolvedlen: size_t) -> *mut c_char);
+libc!(fn __recv_chk(socket: c_int, buf: *mut c_void, len: size_t, buflen: size_t, flags: c_int) -> ssize_t);
+libc!(fn __recvfrom_chk(socket: c_int, buf: *mut c_void, len: size_t, buflen: size_t, flags: c_int, addr: *mut sockaddr, addrlen: *mut socklen_t) -> ssize_t);
+libc!(fn __snprintf_chk(s: *mut c_char, n: size_t, flag: c_int, slen: size_t, format: *const c_char, ...) -> c_int);
+libc!(fn __sprintf_chk(s: *mut c_char, flag: c_int, slen: size_t, format: *const c_char, ...) -> c_int);
+libc!(fn __xstat(ver: c_int, path: *const c_char, buf: *mut stat) -> c_int);
+libc!(fn __xstat64(ver: c_int, path: *const c_char, buf: *mut stat64) -> c_int);
 libc!(fn _exit(status: c_int) -> !);
 libc!(fn abort() -> !);
 libc!(fn abs(i: c_int) -> c_int);
@@ -71,6 +389,7 @@ libc!(fn accept4(fd: c_int, addr: *mut sockaddr, len: *mut socklen_t, flg: c_int
 libc!(fn access(path: *const c_char, amode: c_int) -> c_int);
 libc!(fn acct(filename: *const c_char) -> c_int);
 libc!(fn addmntent(stream: *mut FILE, mnt: *const mntent) -> c_int);
+libc!(fn adjtime(delta: *mut timeval, olddelta: *mut timeval) -> c_int);
 libc!(fn adjtimex(buf: *mut timex) -> c_int);
 libc!(fn aio_cancel(fd: c_int, aiocbp: *mut aiocb) -> c_int);
 libc!(fn aio_error(aiocbp: *const aiocb) -> c_int);
@@ -80,10 +399,16 @@ libc!(fn aio_return(aiocbp: *mut aiocb) -> ssize_t);
 libc!(fn aio_suspend(aiocb_list: *const *const aiocb, nitems: c_int, timeout: *const timespec) -> c_int);
 libc!(fn aio_write(aiocbp: *mut aiocb) -> c_int);
 libc!(fn alarm(seconds: c_uint) -> c_uint);
+libc!(fn aligned_alloc(alignment: size_t, size: size_t) -> *mut c_void);
+libc!(fn asctime_r(tm: *const tm, buf: *mut c_char) -> *mut c_char);
 libc!(fn atexit(cb: extern "C" fn()) -> c_int);
 libc!(fn atof(s: *const c_char) -> c_double);
 libc!(fn atoi(s: *const c_char) -> c_int);
+libc!(fn atol(s: *const c_char) -> c_long);
+libc!(fn atoll(s: *const c_char) -> c_longlong);
 libc!(fn backtrace(buf: *mut *mut c_void, sz: c_int) -> c_int);
+libc!(fn backtrace_symbols(buffer: *const *mut c_void, len: c_int) -> *mut *mut c_char);
+libc!(fn backtrace_symbols_fd(buffer: *const *mut c_void, len: c_int, fd: c_int) -> ());
 libc!(fn bind(socket: c_int, address: *const sockaddr, address_len: socklen_t) -> c_int);
 libc!(fn brk(addr: *mut c_void) -> c_int);
 libc!(fn bsearch(key: *const c_void, base: *const c_void, num: size_t, size: size_t, compar: Option<unsafe extern "C" fn(_: *const c_void, _: *const c_void) -> c_int>) -> *mut c_void);
@@ -100,36 +425,46 @@ libc!(fn chown(path: *const c_char, uid: uid_t, gid: gid_t) -> c_int);
 libc!(fn chroot(name: *const c_char) -> c_int);
 libc!(fn clearenv() -> c_int);
 libc!(fn clearerr(stream: *mut FILE) -> ());
+libc!(fn clock_adjtime(clk_id: clockid_t, buf: *mut timex) -> c_int);
 libc!(fn clock_getcpuclockid(pid: pid_t, clk_id: *mut clockid_t) -> c_int);
 libc!(fn clock_getres(clk_id: clockid_t, tp: *mut timespec) -> c_int);
 libc!(fn clock_gettime(clk_id: clockid_t, tp: *mut timespec) -> c_int);
 libc!(fn clock_nanosleep(clk_id: clockid_t, flags: c_int, rqtp: *const timespec, rmtp: *mut timespec) -> c_int);
 libc!(fn clock_settime(clk_id: clockid_t, tp: *const timespec) -> c_int);
-libc!(fn clone(cb: extern "C" fn(_: *mut c_void) -> c_int, child_stack: *mut c_void, flags: c_int, arg: *mut c_void, ) -> c_int);
+libc!(fn clone(cb: extern "C" fn(_: *mut c_void) -> c_int, child_stack: *mut c_void, flags: c_int, arg: *mut c_void, ...ptid: *mut pid_t, tls: *mut c_void, ctid: *mut pid_t) -> c_int);
 libc!(fn close(fd: c_int) -> c_int);
+libc!(fn close_range(first: c_uint, last: c_uint, flags: c_int) -> c_int);
 libc!(fn closedir(dirp: *mut DIR) -> c_int);
 libc!(fn closelog() -> ());
+libc!(fn confstr(name: c_int, buf: *mut c_char, len: size_t) -> size_t);
 libc!(fn connect(socket: c_int, address: *const sockaddr, len: socklen_t) -> c_int);
 libc!(fn copy_file_range(fd_in: c_int, off_in: *mut off64_t, fd_out: c_int, off_out: *mut off64_t, len: size_t, flags: c_uint) -> ssize_t);
 libc!(fn creat(path: *const c_char, mode: mode_t) -> c_int);
 libc!(fn creat64(path: *const c_char, mode: mode_t) -> c_int);
+libc!(fn ctermid(s: *mut c_char) -> *mut c_char);
+libc!(fn ctime_r(timep: *const time_t, buf: *mut c_char) -> *mut c_char);
 libc!(fn daemon(nochdir: c_int, noclose: c_int) -> c_int);
 libc!(fn difftime(time1: time_t, time0: time_t) -> c_double);
 libc!(fn dirfd(dirp: *mut DIR) -> c_int);
+libc!(fn dirname(path: *mut c_char) -> *mut c_char);
 libc!(fn dl_iterate_phdr(callback: Option<unsafe extern "C" fn(info: *mut dl_phdr_info, size: size_t, data: *mut c_void) -> c_int>, data: *mut c_void) -> c_int);
 libc!(fn dladdr(addr: *const c_void, info: *mut Dl_info) -> c_int);
+libc!(fn dladdr1(addr: *const c_void, info: *mut Dl_info, extra_info: *mut *mut c_void, flags: c_int) -> c_int);
 libc!(fn dlclose(handle: *mut c_void) -> c_int);
 libc!(fn dlerror() -> *mut c_char);
 libc!(fn dlinfo(handle: *mut c_void, request: c_int, info: *mut c_void) -> c_int);
 libc!(fn dlmopen(lmid: Lmid_t, filename: *const c_char, flag: c_int) -> *mut c_void);
 libc!(fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void);
 libc!(fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void);
+libc!(fn dlvsym(handle: *mut c_void, symbol: *const c_char, version: *const c_char) -> *mut c_void);
 libc!(fn dup(fd: c_int) -> c_int);
 libc!(fn dup2(src: c_int, dst: c_int) -> c_int);
 libc!(fn dup3(oldfd: c_int, newfd: c_int, flags: c_int) -> c_int);
 libc!(fn duplocale(base: locale_t) -> locale_t);
+libc!(fn eaccess(pathname: *const c_char, mode: c_int) -> c_int);
 libc!(fn endgrent() -> ());
 libc!(fn endmntent(streamp: *mut FILE) -> c_int);
+libc!(fn endnetent() -> ());
 libc!(fn endpwent() -> ());
 libc!(fn endservent() -> ());
 libc!(fn endspent() -> ());
@@ -138,16 +473,22 @@ libc!(fn epoll_create(size: c_int) -> c_int);
 libc!(fn epoll_create1(flags: c_int) -> c_int);
 libc!(fn epoll_ctl(epfd: c_int, op: c_int, fd: c_int, event: *mut epoll_event) -> c_int);
 libc!(fn epoll_pwait(epfd: c_int, events: *mut epoll_event, maxevents: c_int, timeout: c_int, sigmask: *const sigset_t) -> c_int);
+libc!(fn epoll_pwait2(epfd: c_int, events: *mut epoll_event, maxevents: c_int, timeout: *const timespec, sigmask: *const sigset_t) -> c_int);
 libc!(fn epoll_wait(epfd: c_int, events: *mut epoll_event, maxevents: c_int, timeout: c_int) -> c_int);
+libc!(fn euidaccess(pathname: *const c_char, mode: c_int) -> c_int);
 libc!(fn eventfd(init: c_uint, flags: c_int) -> c_int);
-libc!(fn execl(path: *const c_char, arg0: *const c_char, ) -> c_int);
-libc!(fn execle(path: *const c_char, arg0: *const c_char, ) -> c_int);
-libc!(fn execlp(file: *const c_char, arg0: *const c_char, ) -> c_int);
+libc!(fn eventfd_read(fd: c_int, value: *mut eventfd_t) -> c_int);
+libc!(fn eventfd_write(fd: c_int, value: eventfd_t) -> c_int);
+libc!(fn execl(path: *const c_char, arg0: *const c_char, ...) -> c_int);
+libc!(fn execle(path: *const c_char, arg0: *const c_char, ...) -> c_int);
+libc!(fn execlp(file: *const c_char, arg0: *const c_char, ...) -> c_int);
 libc!(fn execv(prog: *const c_char, argv: *const *const c_char) -> c_int);
 libc!(fn execve(prog: *const c_char, argv: *const *const c_char, envp: *const *const c_char) -> c_int);
+libc!(fn execveat(dirfd: c_int, pathname: *const c_char, argv: *const *mut c_char, envp: *const *mut c_char, flags: c_int) -> c_int);
 libc!(fn execvp(c: *const c_char, argv: *const *const c_char) -> c_int);
 libc!(fn execvpe(file: *const c_char, argv: *const *const c_char, envp: *const *const c_char) -> c_int);
 libc!(fn exit(status: c_int) -> !);
+libc!(fn explicit_bzero(s: *mut c_void, len: size_t) -> ());
 libc!(fn faccessat(dirfd: c_int, pathname: *const c_char, mode: c_int, flags: c_int) -> c_int);
 libc!(fn fallocate(fd: c_int, mode: c_int, offset: off_t, len: off_t) -> c_int);
 libc!(fn fallocate64(fd: c_int, mode: c_int, offset: off64_t, len: off64_t) -> c_int);
@@ -159,7 +500,7 @@ libc!(fn fchmodat(dirfd: c_int, pathname: *const c_char, mode: mode_t, flags: c_
 libc!(fn fchown(fd: c_int, owner: uid_t, group: gid_t) -> c_int);
 libc!(fn fchownat(dirfd: c_int, pathname: *const c_char, owner: uid_t, group: gid_t, flags: c_int) -> c_int);
 libc!(fn fclose(file: *mut FILE) -> c_int);
-libc!(fn fcntl(fd: c_int, cmd: c_int, ) -> c_int);
+libc!(fn fcntl(fd: c_int, cmd: c_int, ...arg: c_ulong) -> c_int);
 libc!(fn fdatasync(fd: c_int) -> c_int);
 libc!(fn fdopen(fd: c_int, mode: *const c_char) -> *mut FILE);
 libc!(fn fdopendir(fd: c_int) -> *mut DIR);
@@ -168,8 +509,10 @@ libc!(fn ferror(stream: *mut FILE) -> c_int);
 libc!(fn fexecve(fd: c_int, argv: *const *const c_char, envp: *const *const c_char) -> c_int);
 libc!(fn fflush(file: *mut FILE) -> c_int);
 libc!(fn fgetc(stream: *mut FILE) -> c_int);
+libc!(fn fgetgrent_r(stream: *mut FILE, grp: *mut group, buf: *mut c_char, buflen: size_t, result: *mut *mut group) -> c_int);
 libc!(fn fgetpos(stream: *mut FILE, ptr: *mut fpos_t) -> c_int);
 libc!(fn fgetpos64(stream: *mut FILE, ptr: *mut fpos64_t) -> c_int);
+libc!(fn fgetpwent_r(stream: *mut FILE, pwd: *mut passwd, buf: *mut c_char, buflen: size_t, result: *mut *mut passwd) -> c_int);
 libc!(fn fgets(buf: *mut c_char, n: c_int, stream: *mut FILE) -> *mut c_char);
 libc!(fn fgetspent_r(fp: *mut FILE, spbuf: *mut spwd, buf: *mut c_char, buflen: size_t, spbufp: *mut *mut spwd) -> c_int);
 libc!(fn fgetxattr(filedes: c_int, name: *const c_char, value: *mut c_void, size: size_t) -> ssize_t);
@@ -177,12 +520,13 @@ libc!(fn fileno(stream: *mut FILE) -> c_int);
 libc!(fn flistxattr(filedes: c_int, list: *mut c_char, size: size_t) -> ssize_t);
 libc!(fn flock(fd: c_int, operation: c_int) -> c_int);
 libc!(fn fmemopen(buf: *mut c_void, size: size_t, mode: *const c_char) -> *mut FILE);
+libc!(fn fnmatch(pattern: *const c_char, name: *const c_char, flags: c_int) -> c_int);
 libc!(fn fopen(filename: *const c_char, mode: *const c_char) -> *mut FILE);
 libc!(fn fopen64(filename: *const c_char, mode: *const c_char) -> *mut FILE);
 libc!(fn fork() -> pid_t);
 libc!(fn forkpty(amaster: *mut c_int, name: *mut c_char, termp: *const termios, winp: *const winsize) -> pid_t);
 libc!(fn fpathconf(filedes: c_int, name: c_int) -> c_long);
-libc!(fn fprintf(stream: *mut FILE, format: *const c_char, ) -> c_int);
+libc!(fn fprintf(stream: *mut FILE, format: *const c_char, ...) -> c_int);
 libc!(fn fputc(c: c_int, stream: *mut FILE) -> c_int);
 libc!(fn fputs(s: *const c_char, stream: *mut FILE) -> c_int);
 libc!(fn fread(ptr: *mut c_void, size: size_t, nobj: size_t, stream: *mut FILE) -> size_t);
@@ -194,7 +538,7 @@ libc!(fn freelocale(loc: locale_t) -> ());
 libc!(fn fremovexattr(filedes: c_int, name: *const c_char) -> c_int);
 libc!(fn freopen(filename: *const c_char, mode: *const c_char, file: *mut FILE) -> *mut FILE);
 libc!(fn freopen64(filename: *const c_char, mode: *const c_char, file: *mut FILE) -> *mut FILE);
-libc!(fn fscanf(stream: *mut FILE, format: *const c_char, ) -> c_int);
+libc!(fn fscanf(stream: *mut FILE, format: *const c_char, ...) -> c_int);
 libc!(fn fseek(stream: *mut FILE, offset: c_long, whence: c_int) -> c_int);
 libc!(fn fseeko(stream: *mut FILE, offset: off_t, whence: c_int) -> c_int);
 libc!(fn fseeko64(stream: *mut FILE, offset: off64_t, whence: c_int) -> c_int);
@@ -229,6 +573,7 @@ libc!(fn getcwd(buf: *mut c_char, size: size_t) -> *mut c_char);
 libc!(fn getdomainname(name: *mut c_char, len: size_t) -> c_int);
 libc!(fn getdtablesize() -> c_int);
 libc!(fn getegid() -> gid_t);
+libc!(fn getentropy(buf: *mut c_void, buflen: size_t) -> c_int);
 libc!(fn getenv(s: *const c_char) -> *mut c_char);
 libc!(fn geteuid() -> uid_t);
 libc!(fn getgid() -> gid_t);
@@ -240,13 +585,27 @@ libc!(fn getgrnam(name: *const c_char) -> *mut group);
 libc!(fn getgrnam_r(name: *const c_char, grp: *mut group, buf: *mut c_char, buflen: size_t, result: *mut *mut group) -> c_int);
 libc!(fn getgrouplist(user: *const c_char, group: gid_t, groups: *mut gid_t, ngroups: *mut c_int) -> c_int);
 libc!(fn getgroups(ngroups_max: c_int, groups: *mut gid_t) -> c_int);
+libc!(fn gethostbyaddr(addr: *const c_void, len: socklen_t, type_: c_int) -> *mut hostent);
+libc!(fn gethostbyaddr_r(addr: *const c_void, len: socklen_t, type_: c_int, ret: *mut hostent, buf: *mut c_char, buflen: size_t, result: *mut *mut hostent, h_errnop: *mut c_int) -> c_int);
+libc!(fn gethostbyname(name: *const c_char) -> *mut hostent);
+libc!(fn gethostbyname2(name: *const c_char, af: c_int) -> *mut hostent);
+libc!(fn gethostbyname_r(name: *const c_char, ret: *mut hostent, buf: *mut c_char, buflen: size_t, result: *mut *mut hostent, h_errnop: *mut c_int) -> c_int);
+libc!(fn gethostid() -> c_long);
 libc!(fn gethostname(name: *mut c_char, len: size_t) -> c_int);
 libc!(fn getifaddrs(ifap: *mut *mut ifaddrs) -> c_int);
+libc!(fn getitimer(which: c_int, curr_value: *mut itimerval) -> c_int);
 libc!(fn getline(lineptr: *mut *mut c_char, n: *mut size_t, stream: *mut FILE) -> ssize_t);
 libc!(fn getloadavg(loadavg: *mut c_double, nelem: c_int) -> c_int);
 libc!(fn getlogin() -> *mut c_char);
 libc!(fn getmntent(stream: *mut FILE) -> *mut mntent);
+libc!(fn getmntent_r(stream: *mut FILE, mntbuf: *mut mntent, buf: *mut c_char, buflen: c_int) -> *mut mntent);
 libc!(fn getnameinfo(sa: *const sockaddr, salen: socklen_t, host: *mut c_char, hostlen: socklen_t, serv: *mut c_char, sevlen: socklen_t, flags: c_int) -> c_int);
+libc!(fn getnetbyaddr(net: u32, type_: c_int) -> *mut netent);
+libc!(fn getnetbyaddr_r(net: u32, type_: c_int, result_buf: *mut netent, buf: *mut c_char, buflen: size_t, result: *mut *mut netent, h_errnop: *mut c_int) -> c_int);
+libc!(fn getnetbyname(name: *const c_char) -> *mut netent);
+libc!(fn getnetbyname_r(name: *const c_char, result_buf: *mut netent, buf: *mut c_char, buflen: size_t, result: *mut *mut netent, h_errnop: *mut c_int) -> c_int);
+libc!(fn getnetent() -> *mut netent);
+libc!(fn getnetent_r(result_buf: *mut netent, buf: *mut c_char, buflen: size_t, result: *mut *mut netent, h_errnop: *mut c_int) -> c_int);
 libc!(fn getopt(argc: c_int, argv: *const *mut c_char, optstr: *const c_char) -> c_int);
 libc!(fn getpeername(socket: c_int, address: *mut sockaddr, address_len: *mut socklen_t) -> c_int);
 libc!(fn getpgid(pid: pid_t) -> pid_t);
@@ -292,8 +651,11 @@ libc!(fn globfree(pglob: *mut glob_t) -> ());
 libc!(fn globfree64(pglob: *mut glob64_t) -> ());
 libc!(fn gmtime(time_p: *const time_t) -> *mut tm);
 libc!(fn gmtime_r(time_p: *const time_t, result: *mut tm) -> *mut tm);
+libc!(fn gnu_get_libc_release() -> *const c_char);
+libc!(fn gnu_get_libc_version() -> *const c_char);
 libc!(fn grantpt(fd: c_int) -> c_int);
 libc!(fn hasmntopt(mnt: *const mntent, opt: *const c_char) -> *mut c_char);
+libc!(fn hstrerror(errcode: c_int) -> *const c_char);
 libc!(fn iconv(cd: iconv_t, inbuf: *mut *mut c_char, inbytesleft: *mut size_t, outbuf: *mut *mut c_char, outbytesleft: *mut size_t) -> size_t);
 libc!(fn iconv_close(cd: iconv_t) -> c_int);
 libc!(fn iconv_open(tocode: *const c_char, fromcode: *const c_char) -> iconv_t);
@@ -301,12 +663,17 @@ libc!(fn if_freenameindex(ptr: *mut if_nameindex) -> ());
 libc!(fn if_indextoname(ifindex: c_uint, ifname: *mut c_char) -> *mut c_char);
 libc!(fn if_nameindex() -> *mut if_nameindex);
 libc!(fn if_nametoindex(ifname: *const c_char) -> c_uint);
+libc!(fn inet_addr(cp: *const c_char) -> in_addr_t);
+libc!(fn inet_aton(cp: *const c_char, inp: *mut in_addr) -> c_int);
+libc!(fn inet_ntoa(in_: in_addr) -> *mut c_char);
+libc!(fn inet_ntop(af: c_int, src: *const c_void, dst: *mut c_char, size: socklen_t) -> *const c_char);
+libc!(fn inet_pton(af: c_int, src: *const c_char, dst: *mut c_void) -> c_int);
 libc!(fn initgroups(user: *const c_char, group: gid_t) -> c_int);
 libc!(fn inotify_add_watch(fd: c_int, path: *const c_char, mask: u32) -> c_int);
 libc!(fn inotify_init() -> c_int);
 libc!(fn inotify_init1(flags: c_int) -> c_int);
 libc!(fn inotify_rm_watch(fd: c_int, wd: c_int) -> c_int);
-libc!(fn ioctl(fd: c_int, request: c_ulong, ) -> c_int);
+libc!(fn ioctl(fd: c_int, request: c_ulong, ...arg: c_ulong) -> c_int);
 libc!(fn ioperm(from: c_ulong, num: c_ulong, turn_on: c_int) -> c_int);
 libc!(fn iopl(level: c_int) -> c_int);
 libc!(fn isalnum(c: c_int) -> c_int);
@@ -324,8 +691,10 @@ libc!(fn isupper(c: c_int) -> c_int);
 libc!(fn isxdigit(c: c_int) -> c_int);
 libc!(fn kill(pid: pid_t, sig: c_int) -> c_int);
 libc!(fn killpg(pgrp: pid_t, sig: c_int) -> c_int);
+libc!(fn klogctl(syslog_type: c_int, bufp: *mut c_char, len: c_int) -> c_int);
 libc!(fn labs(i: c_long) -> c_long);
 libc!(fn lchown(path: *const c_char, uid: uid_t, gid: gid_t) -> c_int);
+libc!(fn lcong48(p: *mut c_ushort) -> ());
 libc!(fn lgetxattr(path: *const c_char, name: *const c_char, value: *mut c_void, size: size_t) -> ssize_t);
 libc!(fn link(src: *const c_char, dst: *const c_char) -> c_int);
 libc!(fn linkat(olddirfd: c_int, oldpath: *const c_char, newdirfd: c_int, newpath: *const c_char, flags: c_int) -> c_int);
@@ -347,18 +716,25 @@ libc!(fn lstat64(path: *const c_char, buf: *mut stat64) -> c_int);
 libc!(fn lutimes(file: *const c_char, times: *const timeval) -> c_int);
 libc!(fn madvise(addr: *mut c_void, len: size_t, advice: c_int) -> c_int);
 libc!(fn major(dev: dev_t) -> c_uint);
-libc!(fn makecontext(ucp: *mut ucontext_t, func: extern "C" fn(), argc: c_int, ) -> ());
+libc!(fn makecontext(ucp: *mut ucontext_t, func: extern "C" fn(), argc: c_int, ...) -> ());
 libc!(fn makedev(major: c_uint, minor: c_uint) -> dev_t);
 libc!(fn mallinfo() -> mallinfo);
+libc!(fn mallinfo2() -> mallinfo2);
 libc!(fn malloc(size: size_t) -> *mut c_void);
+libc!(fn malloc_info(options: c_int, stream: *mut FILE) -> c_int);
+libc!(fn malloc_stats() -> ());
+libc!(fn malloc_trim(__pad: size_t) -> c_int);
 libc!(fn malloc_usable_size(ptr: *mut c_void) -> size_t);
 libc!(fn mallopt(param: c_int, value: c_int) -> c_int);
 libc!(fn memalign(align: size_t, size: size_t) -> *mut c_void);
+libc!(fn memccpy(dest: *mut c_void, src: *const c_void, c: c_int, n: size_t) -> *mut c_void);
 libc!(fn memchr(cx: *const c_void, c: c_int, n: size_t) -> *mut c_void);
 libc!(fn memcmp(cx: *const c_void, ct: *const c_void, n: size_t) -> c_int);
 libc!(fn memcpy(dest: *mut c_void, src: *const c_void, n: size_t) -> *mut c_void);
+libc!(fn memfd_create(name: *const c_char, flags: c_uint) -> c_int);
 libc!(fn memmem(haystack: *const c_void, haystacklen: size_t, needle: *const c_void, needlelen: size_t) -> *mut c_void);
 libc!(fn memmove(dest: *mut c_void, src: *const c_void, n: size_t) -> *mut c_void);
+libc!(fn mempcpy(dest: *mut c_void, src: *const c_void, n: size_t) -> *mut c_void);
 libc!(fn memrchr(cx: *const c_void, c: c_int, n: size_t) -> *mut c_void);
 libc!(fn memset(dest: *mut c_void, c: c_int, n: size_t) -> *mut c_void);
 libc!(fn mincore(addr: *mut c_void, len: size_t, vec: *mut c_uchar) -> c_int);
@@ -376,6 +752,7 @@ libc!(fn mkstemp(template: *mut c_char) -> c_int);
 libc!(fn mkstemps(template: *mut c_char, suffixlen: c_int) -> c_int);
 libc!(fn mktime(tm: *mut tm) -> time_t);
 libc!(fn mlock(addr: *const c_void, len: size_t) -> c_int);
+libc!(fn mlock2(addr: *const c_void, len: size_t, flags: c_uint) -> c_int);
 libc!(fn mlockall(flags: c_int) -> c_int);
 libc!(fn mmap(addr: *mut c_void, len: size_t, prot: c_int, flags: c_int, fd: c_int, offset: off_t) -> *mut c_void);
 libc!(fn mmap64(addr: *mut c_void, len: size_t, prot: c_int, flags: c_int, fd: c_int, offset: off64_t) -> *mut c_void);
@@ -383,14 +760,16 @@ libc!(fn mount(src: *const c_char, target: *const c_char, fstype: *const c_char,
 libc!(fn mprotect(addr: *mut c_void, len: size_t, prot: c_int) -> c_int);
 libc!(fn mq_close(mqd: mqd_t) -> c_int);
 libc!(fn mq_getattr(mqd: mqd_t, attr: *mut mq_attr) -> c_int);
-libc!(fn mq_open(name: *const c_char, oflag: c_int, ) -> mqd_t);
+libc!(fn mq_notify(mqdes: mqd_t, sevp: *const sigevent) -> c_int);
+libc!(fn mq_open(name: *const c_char, oflag: c_int, ...mode: mode_t, attr: *mut mq_attr) -> mqd_t);
 libc!(fn mq_receive(mqd: mqd_t, msg_ptr: *mut c_char, msg_len: size_t, msg_prio: *mut c_uint) -> ssize_t);
 libc!(fn mq_send(mqd: mqd_t, msg_ptr: *const c_char, msg_len: size_t, msg_prio: c_uint) -> c_int);
 libc!(fn mq_setattr(mqd: mqd_t, newattr: *const mq_attr, oldattr: *mut mq_attr) -> c_int);
 libc!(fn mq_timedreceive(mqd: mqd_t, msg_ptr: *mut c_char, msg_len: size_t, msg_prio: *mut c_uint, abs_timeout: *const timespec) -> ssize_t);
 libc!(fn mq_timedsend(mqd: mqd_t, msg_ptr: *const c_char, msg_len: size_t, msg_prio: c_uint, abs_timeout: *const timespec) -> c_int);
 libc!(fn mq_unlink(name: *const c_char) -> c_int);
-libc!(fn mremap(addr: *mut c_void, len: size_t, new_len: size_t, flags: c_int, ) -> *mut c_void);
+libc!(fn mrand48() -> c_long);
+libc!(fn mremap(addr: *mut c_void, len: size_t, new_len: size_t, flags: c_int, ...new_address: *mut c_void) -> *mut c_void);
 libc!(fn msgctl(msqid: c_int, cmd: c_int, buf: *mut msqid_ds) -> c_int);
 libc!(fn msgget(key: key_t, msgflg: c_int) -> c_int);
 libc!(fn msgrcv(msqid: c_int, msgp: *mut c_void, msgsz: size_t, msgtyp: c_long, msgflg: c_int) -> ssize_t);
@@ -399,6 +778,7 @@ libc!(fn msync(addr: *mut c_void, len: size_t, flags: c_int) -> c_int);
 libc!(fn munlock(addr: *const c_void, len: size_t) -> c_int);
 libc!(fn munlockall() -> c_int);
 libc!(fn munmap(addr: *mut c_void, len: size_t) -> c_int);
+libc!(fn name_to_handle_at(dirfd: c_int, path: *const c_char, handle: *mut file_handle, mount_id: *mut c_int, flags: c_int) -> c_int);
 libc!(fn nanosleep(rqtp: *const timespec, rmtp: *mut timespec) -> c_int);
 libc!(fn newlocale(mask: c_int, locale: *const c_char, base: locale_t) -> locale_t);
 libc!(fn nice(incr: c_int) -> c_int);
@@ -406,12 +786,13 @@ libc!(fn nl_langinfo(item: nl_item) -> *mut c_char);
 libc!(fn nl_langinfo_l(item: nl_item, locale: locale_t) -> *mut c_char);
 libc!(fn ntp_adjtime(buf: *mut timex) -> c_int);
 libc!(fn ntp_gettime(buf: *mut ntptimeval) -> c_int);
-libc!(fn open(path: *const c_char, oflag: c_int, ) -> c_int);
-libc!(fn open64(path: *const c_char, oflag: c_int, ) -> c_int);
+libc!(fn open(path: *const c_char, oflag: c_int, ...mode: mode_t) -> c_int);
+libc!(fn open64(path: *const c_char, oflag: c_int, ...mode: mode_t) -> c_int);
+libc!(fn open_by_handle_at(mount_fd: c_int, handle: *mut file_handle, flags: c_int) -> c_int);
 libc!(fn open_memstream(ptr: *mut *mut c_char, sizeloc: *mut size_t) -> *mut FILE);
 libc!(fn open_wmemstream(ptr: *mut *mut wchar_t, sizeloc: *mut size_t) -> *mut FILE);
-libc!(fn openat(dirfd: c_int, pathname: *const c_char, flags: c_int, ) -> c_int);
-libc!(fn openat64(fd: c_int, path: *const c_char, oflag: c_int, ) -> c_int);
+libc!(fn openat(dirfd: c_int, pathname: *const c_char, flags: c_int, ...mode: mode_t) -> c_int);
+libc!(fn openat64(dirfd: c_int, pathname: *const c_char, flags: c_int, ...mode: mode_t) -> c_int);
 libc!(fn opendir(dirname: *const c_char) -> *mut DIR);
 libc!(fn openlog(ident: *const c_char, logopt: c_int, facility: c_int) -> ());
 libc!(fn openpty(amaster: *mut c_int, aslave: *mut c_int, name: *mut c_char, termp: *const termios, winp: *const winsize) -> c_int);
@@ -420,6 +801,9 @@ libc!(fn pause() -> c_int);
 libc!(fn pclose(stream: *mut FILE) -> c_int);
 libc!(fn perror(s: *const c_char) -> ());
 libc!(fn personality(persona: c_ulong) -> c_int);
+libc!(fn pidfd_getfd(pidfd: c_int, targetfd: c_int, flags: c_uint) -> c_int);
+libc!(fn pidfd_open(pid: pid_t, flags: c_uint) -> c_int);
+libc!(fn pidfd_send_signal(pidfd: c_int, sig: c_int, info: *mut siginfo_t, flags: c_uint) -> c_int);
 libc!(fn pipe(fds: *mut c_int) -> c_int);
 libc!(fn pipe2(fds: *mut c_int, flags: c_int) -> c_int);
 libc!(fn poll(fds: *mut pollfd, nfds: nfds_t, timeout: c_int) -> c_int);
@@ -432,9 +816,13 @@ libc!(fn posix_madvise(addr: *mut c_void, len: size_t, advice: c_int) -> c_int);
 libc!(fn posix_memalign(memptr: *mut *mut c_void, align: size_t, size: size_t) -> c_int);
 libc!(fn posix_openpt(flags: c_int) -> c_int);
 libc!(fn posix_spawn(pid: *mut pid_t, path: *const c_char, file_actions: *const posix_spawn_file_actions_t, attrp: *const posix_spawnattr_t, argv: *const *mut c_char, envp: *const *mut c_char) -> c_int);
+libc!(fn posix_spawn_file_actions_addchdir_np(actions: *mut posix_spawn_file_actions_t, path: *const c_char) -> c_int);
 libc!(fn posix_spawn_file_actions_addclose(actions: *mut posix_spawn_file_actions_t, fd: c_int) -> c_int);
+libc!(fn posix_spawn_file_actions_addclosefrom_np(actions: *mut posix_spawn_file_actions_t, from: c_int) -> c_int);
 libc!(fn posix_spawn_file_actions_adddup2(actions: *mut posix_spawn_file_actions_t, fd: c_int, newfd: c_int) -> c_int);
+libc!(fn posix_spawn_file_actions_addfchdir_np(actions: *mut posix_spawn_file_actions_t, fd: c_int) -> c_int);
 libc!(fn posix_spawn_file_actions_addopen(actions: *mut posix_spawn_file_actions_t, fd: c_int, path: *const c_char, oflag: c_int, mode: mode_t) -> c_int);
+libc!(fn posix_spawn_file_actions_addtcsetpgrp_np(actions: *mut posix_spawn_file_actions_t, tcfd: c_int) -> c_int);
 libc!(fn posix_spawn_file_actions_destroy(actions: *mut posix_spawn_file_actions_t) -> c_int);
 libc!(fn posix_spawn_file_actions_init(actions: *mut posix_spawn_file_actions_t) -> c_int);
 libc!(fn posix_spawnattr_destroy(attr: *mut posix_spawnattr_t) -> c_int);
@@ -453,13 +841,14 @@ libc!(fn posix_spawnattr_setsigdefault(attr: *mut posix_spawnattr_t, default: *c
 libc!(fn posix_spawnattr_setsigmask(attr: *mut posix_spawnattr_t, default: *const sigset_t) -> c_int);
 libc!(fn posix_spawnp(pid: *mut pid_t, file: *const c_char, file_actions: *const posix_spawn_file_actions_t, attrp: *const posix_spawnattr_t, argv: *const *mut c_char, envp: *const *mut c_char) -> c_int);
 libc!(fn ppoll(fds: *mut pollfd, nfds: nfds_t, timeout: *const timespec, sigmask: *const sigset_t) -> c_int);
-libc!(fn prctl(option: c_int, ) -> c_int);
+libc!(fn prctl(option: c_int, ...arg2: c_ulong, arg3: c_ulong, arg4: c_ulong, arg5: c_ulong) -> c_int);
 libc!(fn pread(fd: c_int, buf: *mut c_void, count: size_t, offset: off_t) -> ssize_t);
 libc!(fn pread64(fd: c_int, buf: *mut c_void, count: size_t, offset: off64_t) -> ssize_t);
 libc!(fn preadv(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off_t) -> ssize_t);
 libc!(fn preadv2(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off_t, flags: c_int) -> ssize_t);
 libc!(fn preadv64(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off64_t) -> ssize_t);
-libc!(fn printf(format: *const c_char, ) -> c_int);
+libc!(fn preadv64v2(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off64_t, flags: c_int) -> ssize_t);
+libc!(fn printf(format: *const c_char, ...) -> c_int);
 libc!(fn prlimit(pid: pid_t, resource: __rlimit_resource_t, new_limit: *const rlimit, old_limit: *mut rlimit) -> c_int);
 libc!(fn prlimit64(pid: pid_t, resource: __rlimit_resource_t, new_limit: *const rlimit64, old_limit: *mut rlimit64) -> c_int);
 libc!(fn process_vm_readv(pid: pid_t, local_iov: *const iovec, liovcnt: c_ulong, remote_iov: *const iovec, riovcnt: c_ulong, flags: c_ulong) -> isize);
@@ -469,11 +858,27 @@ libc!(fn pthread_atfork(prepare: Option<unsafe extern "C" fn()>, parent: Option<
 libc!(fn pthread_attr_destroy(attr: *mut pthread_attr_t) -> c_int);
 libc!(fn pthread_attr_getaffinity_np(attr: *const pthread_attr_t, cpusetsize: size_t, cpuset: *mut cpu_set_t) -> c_int);
 libc!(fn pthread_attr_getguardsize(attr: *const pthread_attr_t, guardsize: *mut size_t) -> c_int);
+libc!(fn pthread_attr_getinheritsched(attr: *const pthread_attr_t, inheritsched: *mut c_int) -> c_int);
+libc!(fn pthread_attr_getschedparam(attr: *const pthread_attr_t, param: *mut sched_param) -> c_int);
+libc!(fn pthread_attr_getschedpolicy(attr: *const pthread_attr_t, policy: *mut c_int) -> c_int);
 libc!(fn pthread_attr_getstack(attr: *const pthread_attr_t, stackaddr: *mut *mut c_void, stacksize: *mut size_t) -> c_int);
+libc!(fn pthread_attr_getstacksize(attr: *const pthread_attr_t, stacksize: *mut size_t) -> c_int);
 libc!(fn pthread_attr_init(attr: *mut pthread_attr_t) -> c_int);
 libc!(fn pthread_attr_setaffinity_np(attr: *mut pthread_attr_t, cpusetsize: size_t, cpuset: *const cpu_set_t) -> c_int);
 libc!(fn pthread_attr_setdetachstate(attr: *mut pthread_attr_t, state: c_int) -> c_int);
+libc!(fn pthread_attr_setguardsize(attr: *mut pthread_attr_t, guardsize: size_t) -> c_int);
+libc!(fn pthread_attr_setinheritsched(attr: *mut pthread_attr_t, inheritsched: c_int) -> c_int);
+libc!(fn pthread_attr_setschedparam(attr: *mut pthread_attr_t, param: *const sched_param) -> c_int);
+libc!(fn pthread_attr_setschedpolicy(attr: *mut pthread_attr_t, policy: c_int) -> c_int);
+libc!(fn pthread_attr_setstack(attr: *mut pthread_attr_t, stackaddr: *mut c_void, stacksize: size_t) -> c_int);
 libc!(fn pthread_attr_setstacksize(attr: *mut pthread_attr_t, stack_size: size_t) -> c_int);
+libc!(fn pthread_barrier_destroy(barrier: *mut pthread_barrier_t) -> c_int);
+libc!(fn pthread_barrier_init(barrier: *mut pthread_barrier_t, attr: *const pthread_barrierattr_t, count: c_uint) -> c_int);
+libc!(fn pthread_barrier_wait(barrier: *mut pthread_barrier_t) -> c_int);
+libc!(fn pthread_barrierattr_destroy(attr: *mut pthread_barrierattr_t) -> c_int);
+libc!(fn pthread_barrierattr_getpshared(attr: *const pthread_barrierattr_t, shared: *mut c_int) -> c_int);
+libc!(fn pthread_barrierattr_init(attr: *mut pthread_barrierattr_t) -> c_int);
+libc!(fn pthread_barrierattr_setpshared(attr: *mut pthread_barrierattr_t, shared: c_int) -> c_int);
 libc!(fn pthread_cancel(thread: pthread_t) -> c_int);
 libc!(fn pthread_cond_broadcast(cond: *mut pthread_cond_t) -> c_int);
 libc!(fn pthread_cond_destroy(cond: *mut pthread_cond_t) -> c_int);
@@ -489,9 +894,11 @@ libc!(fn pthread_condattr_setclock(attr: *mut pthread_condattr_t, clock_id: cloc
 libc!(fn pthread_condattr_setpshared(attr: *mut pthread_condattr_t, pshared: c_int) -> c_int);
 libc!(fn pthread_create(native: *mut pthread_t, attr: *const pthread_attr_t, f: extern "C" fn(_: *mut c_void) -> *mut c_void, value: *mut c_void) -> c_int);
 libc!(fn pthread_detach(thread: pthread_t) -> c_int);
+libc!(fn pthread_equal(t1: pthread_t, t2: pthread_t) -> c_int);
 libc!(fn pthread_exit(value: *mut c_void) -> !);
 libc!(fn pthread_getaffinity_np(thread: pthread_t, cpusetsize: size_t, cpuset: *mut cpu_set_t) -> c_int);
 libc!(fn pthread_getattr_np(native: pthread_t, attr: *mut pthread_attr_t) -> c_int);
+libc!(fn pthread_getcpuclockid(thread: pthread_t, clk_id: *mut clockid_t) -> c_int);
 libc!(fn pthread_getname_np(thread: pthread_t, name: *mut c_char, len: size_t) -> c_int);
 libc!(fn pthread_getschedparam(native: pthread_t, policy: *mut c_int, param: *mut sched_param) -> c_int);
 libc!(fn pthread_getspecific(key: pthread_key_t) -> *mut c_void);
@@ -499,6 +906,7 @@ libc!(fn pthread_join(native: pthread_t, value: *mut *mut c_void) -> c_int);
 libc!(fn pthread_key_create(key: *mut pthread_key_t, dtor: Option<unsafe extern "C" fn(_: *mut c_void)>) -> c_int);
 libc!(fn pthread_key_delete(key: pthread_key_t) -> c_int);
 libc!(fn pthread_kill(thread: pthread_t, sig: c_int) -> c_int);
+libc!(fn pthread_mutex_consistent(mutex: *mut pthread_mutex_t) -> c_int);
 libc!(fn pthread_mutex_destroy(lock: *mut pthread_mutex_t) -> c_int);
 libc!(fn pthread_mutex_init(lock: *mut pthread_mutex_t, attr: *const pthread_mutexattr_t) -> c_int);
 libc!(fn pthread_mutex_lock(lock: *mut pthread_mutex_t) -> c_int);
@@ -506,10 +914,15 @@ libc!(fn pthread_mutex_timedlock(lock: *mut pthread_mutex_t, abstime: *const tim
 libc!(fn pthread_mutex_trylock(lock: *mut pthread_mutex_t) -> c_int);
 libc!(fn pthread_mutex_unlock(lock: *mut pthread_mutex_t) -> c_int);
 libc!(fn pthread_mutexattr_destroy(attr: *mut pthread_mutexattr_t) -> c_int);
+libc!(fn pthread_mutexattr_getprotocol(attr: *const pthread_mutexattr_t, protocol: *mut c_int) -> c_int);
 libc!(fn pthread_mutexattr_getpshared(attr: *const pthread_mutexattr_t, pshared: *mut c_int) -> c_int);
+libc!(fn pthread_mutexattr_getrobust(attr: *const pthread_mutexattr_t, robustness: *mut c_int) -> c_int);
 libc!(fn pthread_mutexattr_init(attr: *mut pthread_mutexattr_t) -> c_int);
+libc!(fn pthread_mutexattr_setprotocol(attr: *mut pthread_mutexattr_t, protocol: c_int) -> c_int);
 libc!(fn pthread_mutexattr_setpshared(attr: *mut pthread_mutexattr_t, pshared: c_int) -> c_int);
+libc!(fn pthread_mutexattr_setrobust(attr: *mut pthread_mutexattr_t, robustness: c_int) -> c_int);
 libc!(fn pthread_mutexattr_settype(attr: *mut pthread_mutexattr_t, _type: c_int) -> c_int);
+libc!(fn pthread_once(control: *mut pthread_once_t, routine: extern "C" fn()) -> c_int);
 libc!(fn pthread_rwlock_destroy(lock: *mut pthread_rwlock_t) -> c_int);
 libc!(fn pthread_rwlock_init(lock: *mut pthread_rwlock_t, attr: *const pthread_rwlockattr_t) -> c_int);
 libc!(fn pthread_rwlock_rdlock(lock: *mut pthread_rwlock_t) -> c_int);
@@ -530,12 +943,22 @@ libc!(fn pthread_setschedparam(native: pthread_t, policy: c_int, param: *const s
 libc!(fn pthread_setschedprio(native: pthread_t, priority: c_int) -> c_int);
 libc!(fn pthread_setspecific(key: pthread_key_t, value: *const c_void) -> c_int);
 libc!(fn pthread_sigmask(how: c_int, set: *const sigset_t, oldset: *mut sigset_t) -> c_int);
-libc!(fn ptrace(request: c_uint, ) -> c_long);
+libc!(fn pthread_sigqueue(thread: pthread_t, sig: c_int, value: sigval) -> c_int);
+libc!(fn pthread_spin_destroy(lock: *mut pthread_spinlock_t) -> c_int);
+libc!(fn pthread_spin_init(lock: *mut pthread_spinlock_t, pshared: c_int) -> c_int);
+libc!(fn pthread_spin_lock(lock: *mut pthread_spinlock_t) -> c_int);
+libc!(fn pthread_spin_trylock(lock: *mut pthread_spinlock_t) -> c_int);
+libc!(fn pthread_spin_unlock(lock: *mut pthread_spinlock_t) -> c_int);
+libc!(fn pthread_timedjoin_np(thread: pthread_t, retval: *mut *mut c_void, abstime: *const timespec) -> c_int);
+libc!(fn pthread_tryjoin_np(thread: pthread_t, retval: *mut *mut c_void) -> c_int);
+libc!(fn ptrace(request: c_uint, ...pid: pid_t, addr: *mut c_void, data: *mut c_void) -> c_long);
 libc!(fn ptsname(fd: c_int) -> *mut c_char);
 libc!(fn ptsname_r(fd: c_int, buf: *mut c_char, buflen: size_t) -> c_int);
 libc!(fn putchar(c: c_int) -> c_int);
 libc!(fn putchar_unlocked(c: c_int) -> c_int);
 libc!(fn putenv(string: *mut c_char) -> c_int);
+libc!(fn putgrent(grp: *const group, stream: *mut FILE) -> c_int);
+libc!(fn putpwent(p: *const passwd, stream: *mut FILE) -> c_int);
 libc!(fn puts(s: *const c_char) -> c_int);
 libc!(fn pututxline(ut: *const utmpx) -> *mut utmpx);
 libc!(fn pwrite(fd: c_int, buf: *const c_void, count: size_t, offset: off_t) -> ssize_t);
@@ -543,6 +966,7 @@ libc!(fn pwrite64(fd: c_int, buf: *const c_void, count: size_t, offset: off64_t)
 libc!(fn pwritev(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off_t) -> ssize_t);
 libc!(fn pwritev2(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off_t, flags: c_int) -> ssize_t);
 libc!(fn pwritev64(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off64_t) -> ssize_t);
+libc!(fn pwritev64v2(fd: c_int, iov: *const iovec, iovcnt: c_int, offset: off64_t, flags: c_int) -> ssize_t);
 libc!(fn qsort(base: *mut c_void, num: size_t, size: size_t, compar: Option<unsafe extern "C" fn(_: *const c_void, _: *const c_void) -> c_int>) -> ());
 libc!(fn qsort_r(base: *mut c_void, num: size_t, size: size_t, compar: Option<unsafe extern "C" fn(_: *const c_void, _: *const c_void, _: *mut c_void) -> c_int>, arg: *mut c_void) -> ());
 libc!(fn quotactl(cmd: c_int, special: *const c_char, id: c_int, data: *mut c_char) -> c_int);
@@ -558,6 +982,7 @@ libc!(fn readlink(path: *const c_char, buf: *mut c_char, bufsz: size_t) -> ssize
 libc!(fn readlinkat(dirfd: c_int, pathname: *const c_char, buf: *mut c_char, bufsiz: size_t) -> ssize_t);
 libc!(fn readv(fd: c_int, iov: *const iovec, iovcnt: c_int) -> ssize_t);
 libc!(fn realloc(p: *mut c_void, size: size_t) -> *mut c_void);
+libc!(fn reallocarray(ptr: *mut c_void, nmemb: size_t, size: size_t) -> *mut c_void);
 libc!(fn realpath(pathname: *const c_char, resolved: *mut c_char) -> *mut c_char);
 libc!(fn reboot(how_to: c_int) -> c_int);
 libc!(fn recv(socket: c_int, buf: *mut c_void, len: size_t, flags: c_int) -> ssize_t);
@@ -579,7 +1004,7 @@ libc!(fn rewind(stream: *mut FILE) -> ());
 libc!(fn rewinddir(dirp: *mut DIR) -> ());
 libc!(fn rmdir(path: *const c_char) -> c_int);
 libc!(fn sbrk(increment: intptr_t) -> *mut c_void);
-libc!(fn scanf(format: *const c_char, ) -> c_int);
+libc!(fn scanf(format: *const c_char, ...) -> c_int);
 libc!(fn sched_get_priority_max(policy: c_int) -> c_int);
 libc!(fn sched_get_priority_min(policy: c_int) -> c_int);
 libc!(fn sched_getaffinity(pid: pid_t, cpusetsize: size_t, cpuset: *mut cpu_set_t) -> c_int);
@@ -591,19 +1016,20 @@ libc!(fn sched_setaffinity(pid: pid_t, cpusetsize: size_t, cpuset: *const cpu_se
 libc!(fn sched_setparam(pid: pid_t, param: *const sched_param) -> c_int);
 libc!(fn sched_setscheduler(pid: pid_t, policy: c_int, param: *const sched_param) -> c_int);
 libc!(fn sched_yield() -> c_int);
+libc!(fn seed48(xseed: *mut c_ushort) -> *mut c_ushort);
 libc!(fn seekdir(dirp: *mut DIR, loc: c_long) -> ());
 libc!(fn select(nfds: c_int, readfs: *mut fd_set, writefds: *mut fd_set, errorfds: *mut fd_set, timeout: *mut timeval) -> c_int);
 libc!(fn sem_close(sem: *mut sem_t) -> c_int);
 libc!(fn sem_destroy(sem: *mut sem_t) -> c_int);
 libc!(fn sem_getvalue(sem: *mut sem_t, sval: *mut c_int) -> c_int);
 libc!(fn sem_init(sem: *mut sem_t, pshared: c_int, value: c_uint) -> c_int);
-libc!(fn sem_open(name: *const c_char, oflag: c_int, ) -> *mut sem_t);
+libc!(fn sem_open(name: *const c_char, oflag: c_int, ...mode: mode_t, value: c_uint) -> *mut sem_t);
 libc!(fn sem_post(sem: *mut sem_t) -> c_int);
 libc!(fn sem_timedwait(sem: *mut sem_t, abstime: *const timespec) -> c_int);
 libc!(fn sem_trywait(sem: *mut sem_t) -> c_int);
 libc!(fn sem_unlink(name: *const c_char) -> c_int);
 libc!(fn sem_wait(sem: *mut sem_t) -> c_int);
-libc!(fn semctl(semid: c_int, semnum: c_int, cmd: c_int, ) -> c_int);
+libc!(fn semctl(semid: c_int, semnum: c_int, cmd: c_int, ...arg: c_ulong) -> c_int);
 libc!(fn semget(key: key_t, nsems: c_int, semflag: c_int) -> c_int);
 libc!(fn semop(semid: c_int, sops: *mut sembuf, nsops: size_t) -> c_int);
 libc!(fn send(socket: c_int, buf: *const c_void, len: size_t, flags: c_int) -> ssize_t);
@@ -623,10 +1049,13 @@ libc!(fn setfsuid(uid: uid_t) -> c_int);
 libc!(fn setgid(gid: gid_t) -> c_int);
 libc!(fn setgrent() -> ());
 libc!(fn setgroups(ngroups: size_t, ptr: *const gid_t) -> c_int);
+libc!(fn sethostid(hostid: c_long) -> c_int);
 libc!(fn sethostname(name: *const c_char, len: size_t) -> c_int);
+libc!(fn setitimer(which: c_int, new_value: *const itimerval, old_value: *mut itimerval) -> c_int);
 libc!(fn setlocale(category: c_int, locale: *const c_char) -> *mut c_char);
 libc!(fn setlogmask(maskpri: c_int) -> c_int);
 libc!(fn setmntent(filename: *const c_char, ty: *const c_char) -> *mut FILE);
+libc!(fn setnetent(stayopen: c_int) -> ());
 libc!(fn setns(fd: c_int, nstype: c_int) -> c_int);
 libc!(fn setpgid(pid: pid_t, pgid: pid_t) -> c_int);
 libc!(fn setpriority(which: __priority_which_t, who: id_t, prio: c_int) -> c_int);
@@ -665,18 +1094,19 @@ libc!(fn signal(signum: c_int, handler: sighandler_t) -> sighandler_t);
 libc!(fn signalfd(fd: c_int, mask: *const sigset_t, flags: c_int) -> c_int);
 libc!(fn sigpending(set: *mut sigset_t) -> c_int);
 libc!(fn sigprocmask(how: c_int, set: *const sigset_t, oldset: *mut sigset_t) -> c_int);
+libc!(fn sigqueue(pid: pid_t, sig: c_int, value: sigval) -> c_int);
 libc!(fn sigsuspend(mask: *const sigset_t) -> c_int);
 libc!(fn sigtimedwait(set: *const sigset_t, info: *mut siginfo_t, timeout: *const timespec) -> c_int);
 libc!(fn sigwait(set: *const sigset_t, sig: *mut c_int) -> c_int);
 libc!(fn sigwaitinfo(set: *const sigset_t, info: *mut siginfo_t) -> c_int);
 libc!(fn sleep(secs: c_uint) -> c_uint);
-libc!(fn snprintf(s: *mut c_char, n: size_t, format: *const c_char, ) -> c_int);
+libc!(fn snprintf(s: *mut c_char, n: size_t, format: *const c_char, ...) -> c_int);
 libc!(fn socket(domain: c_int, ty: c_int, protocol: c_int) -> c_int);
 libc!(fn socketpair(domain: c_int, type_: c_int, protocol: c_int, socket_vector: *mut c_int) -> c_int);
 libc!(fn splice(fd_in: c_int, off_in: *mut loff_t, fd_out: c_int, off_out: *mut loff_t, len: size_t, flags: c_uint) -> ssize_t);
-libc!(fn sprintf(s: *mut c_char, format: *const c_char, ) -> c_int);
+libc!(fn sprintf(s: *mut c_char, format: *const c_char, ...) -> c_int);
 libc!(fn srand(seed: c_uint) -> ());
-libc!(fn sscanf(s: *const c_char, format: *const c_char, ) -> c_int);
+libc!(fn sscanf(s: *const c_char, format: *const c_char, ...) -> c_int);
 libc!(fn stat(path: *const c_char, buf: *mut stat) -> c_int);
 libc!(fn stat64(path: *const c_char, buf: *mut stat64) -> c_int);
 libc!(fn statfs(path: *const c_char, buf: *mut statfs) -> c_int);
@@ -684,10 +1114,13 @@ libc!(fn statfs64(path: *const c_char, buf: *mut statfs64) -> c_int);
 libc!(fn statvfs(path: *const c_char, buf: *mut statvfs) -> c_int);
 libc!(fn statvfs64(path: *const c_char, buf: *mut statvfs64) -> c_int);
 libc!(fn statx(dirfd: c_int, pathname: *const c_char, flags: c_int, mask: c_uint, statxbuf: *mut statx) -> c_int);
+libc!(fn stpcpy(dst: *mut c_char, src: *const c_char) -> *mut c_char);
+libc!(fn stpncpy(dst: *mut c_char, src: *const c_char, n: size_t) -> *mut c_char);
 libc!(fn strcasecmp(s1: *const c_char, s2: *const c_char) -> c_int);
 libc!(fn strcasestr(cs: *const c_char, ct: *const c_char) -> *mut c_char);
 libc!(fn strcat(s: *mut c_char, ct: *const c_char) -> *mut c_char);
 libc!(fn strchr(cs: *const c_char, c: c_int) -> *mut c_char);
+libc!(fn strchrnul(s: *const c_char, c: c_int) -> *mut c_char);
 libc!(fn strcmp(cs: *const c_char, ct: *const c_char) -> c_int);
 libc!(fn strcoll(cs: *const c_char, ct: *const c_char) -> c_int);
 libc!(fn strcpy(dst: *mut c_char, src: *const c_char) -> *mut c_char);
@@ -695,6 +1128,8 @@ libc!(fn strcspn(cs: *const c_char, ct: *const c_char) -> size_t);
 libc!(fn strdup(cs: *const c_char) -> *mut c_char);
 libc!(fn strerror(n: c_int) -> *mut c_char);
 libc!(fn strerror_r(errnum: c_int, buf: *mut c_char, buflen: size_t) -> c_int);
+libc!(fn strftime(s: *mut c_char, max: size_t, format: *const c_char, tm: *const tm) -> size_t);
+libc!(fn strftime_l(s: *mut c_char, max: size_t, format: *const c_char, tm: *const tm, locale: locale_t) -> size_t);
 libc!(fn strlen(cs: *const c_char) -> size_t);
 libc!(fn strncasecmp(s1: *const c_char, s2: *const c_char, n: size_t) -> c_int);
 libc!(fn strncat(s: *mut c_char, ct: *const c_char, n: size_t) -> *mut c_char);
@@ -703,14 +1138,19 @@ libc!(fn strncpy(dst: *mut c_char, src: *const c_char, n: size_t) -> *mut c_char
 libc!(fn strndup(cs: *const c_char, n: size_t) -> *mut c_char);
 libc!(fn strnlen(cs: *const c_char, maxlen: size_t) -> size_t);
 libc!(fn strpbrk(cs: *const c_char, ct: *const c_char) -> *mut c_char);
+libc!(fn strptime(s: *const c_char, format: *const c_char, tm: *mut tm) -> *mut c_char);
 libc!(fn strrchr(cs: *const c_char, c: c_int) -> *mut c_char);
 libc!(fn strsignal(sig: c_int) -> *mut c_char);
 libc!(fn strspn(cs: *const c_char, ct: *const c_char) -> size_t);
 libc!(fn strstr(cs: *const c_char, ct: *const c_char) -> *mut c_char);
 libc!(fn strtod(s: *const c_char, endp: *mut *mut c_char) -> c_double);
+libc!(fn strtof(s: *const c_char, endp: *mut *mut c_char) -> c_float);
 libc!(fn strtok(s: *mut c_char, t: *const c_char) -> *mut c_char);
+libc!(fn strtok_r(s: *mut c_char, t: *const c_char, p: *mut *mut c_char) -> *mut c_char);
 libc!(fn strtol(s: *const c_char, endp: *mut *mut c_char, base: c_int) -> c_long);
+libc!(fn strtoll(s: *const c_char, endp: *mut *mut c_char, base: c_int) -> c_longlong);
 libc!(fn strtoul(s: *const c_char, endp: *mut *mut c_char, base: c_int) -> c_ulong);
+libc!(fn strtoull(s: *const c_char, endp: *mut *mut c_char, base: c_int) -> c_ulonglong);
 libc!(fn strxfrm(s: *mut c_char, ct: *const c_char, n: size_t) -> size_t);
 libc!(fn swapcontext(uocp: *mut ucontext_t, ucp: *const ucontext_t) -> c_int);
 libc!(fn swapoff(path: *const c_char) -> c_int);
@@ -719,11 +1159,12 @@ libc!(fn symlink(path1: *const c_char, path2: *const c_char) -> c_int);
 libc!(fn symlinkat(target: *const c_char, newdirfd: c_int, linkpath: *const c_char) -> c_int);
 libc!(fn sync() -> ());
 libc!(fn sync_file_range(fd: c_int, offset: off64_t, nbytes: off64_t, flags: c_uint) -> c_int);
-libc!(fn syscall(num: c_long, ) -> c_long);
+libc!(fn syncfs(fd: c_int) -> c_int);
+libc!(fn syscall(num: c_long, ...arg1: c_long, arg2: c_long, arg3: c_long, arg4: c_long, arg5: c_long, arg6: c_long) -> c_long);
 libc!(fn sysconf(name: c_int) -> c_long);
 libc!(fn sysctl(name: *mut c_int, namelen: c_int, oldp: *mut c_void, oldlenp: *mut size_t, newp: *mut c_void, newlen: size_t) -> c_int);
 libc!(fn sysinfo(info: *mut sysinfo) -> c_int);
-libc!(fn syslog(priority: c_int, message: *const c_char, ) -> ());
+libc!(fn syslog(priority: c_int, message: *const c_char, ...) -> ());
 libc!(fn system(s: *const c_char) -> c_int);
 libc!(fn tcdrain(fd: c_int) -> c_int);
 libc!(fn tcflow(fd: c_int, action: c_int) -> c_int);
@@ -736,6 +1177,7 @@ libc!(fn tcsetattr(fd: c_int, optional_actions: c_int, termios: *const termios)
 libc!(fn tcsetpgrp(fd: c_int, pgrp: pid_t) -> c_int);
 libc!(fn tee(fd_in: c_int, fd_out: c_int, len: size_t, flags: c_uint) -> ssize_t);
 libc!(fn telldir(dirp: *mut DIR) -> c_long);
+libc!(fn tgkill(tgid: pid_t, tid: pid_t, sig: c_int) -> c_int);
 libc!(fn time(time: *mut time_t) -> time_t);
 libc!(fn timegm(tm: *mut tm) -> time_t);
 libc!(fn timerfd_create(clockid: c_int, flags: c_int) -> c_int);
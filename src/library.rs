@@ -0,0 +1,181 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use crate::{cache, workspace, Diagnostic};
+
+/// A compiled hook library
+///
+/// It is returned by [Anchor::build](crate::Anchor::build) and
+/// [Anchor::build_to](crate::Anchor::build_to), or opened from a library built earlier, and can
+/// be attached to any number of commands without rebuilding it
+#[derive(Debug, Clone)]
+pub struct HookLibrary {
+    path: PathBuf,
+    /// Set when the library lives in a memfd instead of a file, see [HookLibrary::memfd]
+    memfd: Option<Arc<OwnedFd>>,
+    /// The compiler warnings of the build that produced the library, see [HookLibrary::warnings]
+    warnings: Vec<Diagnostic>,
+}
+
+impl HookLibrary {
+    /// Use an already compiled hook library
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if !path.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no hook library at {}", path.display()),
+            ));
+        }
+        // LD_PRELOAD needs an absolute path, the child might run in another directory
+        Ok(Self::new(path.canonicalize()?))
+    }
+
+    /// Use a hook library embedded in the program, see [embed](crate::embed)
+    ///
+    /// The library has to be a file to be preloaded, so it is written to the default workspace
+    /// (named after its hash, it is only written once)
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let workspace = workspace::resolve(None);
+        workspace::create(&workspace)?;
+        let dir = workspace.join("embedded");
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{}.so", cache::key(&[bytes])));
+        if !path.exists() {
+            crate::write_atomic(&path, |tmp| std::fs::write(tmp, bytes))?;
+        }
+        Ok(Self::new(path))
+    }
+
+    /// Load a hook library in an anonymous in-memory file (`memfd_create`) instead of a file on
+    /// disk
+    ///
+    /// Nothing is written to the file system, so there is no file left behind and no file another
+    /// user could tamper with. The memfd is sealed, it can't be modified once loaded.
+    ///
+    /// The commands the library is attached to inherit the file descriptor, and `LD_PRELOAD`
+    /// points to it through `/proc/self/fd`
+    #[cfg(target_os = "linux")]
+    pub fn memfd(bytes: &[u8]) -> io::Result<Self> {
+        let fd = unsafe {
+            libc::memfd_create(
+                "rhookdyl\0".as_ptr() as _,
+                libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING,
+            )
+        };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut file = unsafe { File::from_raw_fd(fd) };
+        file.write_all(bytes)?;
+        let seals = libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE | libc::F_SEAL_SEAL;
+        if unsafe { libc::fcntl(fd, libc::F_ADD_SEALS, seals) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            path: PathBuf::from(format!("/proc/self/fd/{}", fd)),
+            memfd: Some(Arc::new(file.into())),
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Copy this library into a memfd, see [HookLibrary::memfd]
+    #[cfg(target_os = "linux")]
+    pub fn to_memfd(&self) -> io::Result<Self> {
+        if self.memfd.is_some() {
+            return Ok(self.clone());
+        }
+        Self::memfd(&std::fs::read(&self.path)?)
+    }
+
+    /// The path of the library
+    ///
+    /// For a library in a memfd this is a `/proc/self/fd` path, valid in this process and in the
+    /// commands the library is attached to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The compiler warnings about the hooks, when the library was just built by
+    /// [Anchor::build](crate::Anchor::build) or [Anchor::build_to](crate::Anchor::build_to)
+    ///
+    /// A library taken from the cache, opened or embedded has no warnings
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    /// Preload the library in the command, so its hooks are active when it runs
+    pub fn attach<'c>(&self, command: &'c mut Command) -> &'c mut Command {
+        command.env("LD_PRELOAD", &self.path);
+        if let Some(memfd) = &self.memfd {
+            // the command keeps the memfd open until it is dropped
+            let memfd = Arc::clone(memfd);
+            // the memfd is close-on-exec so other commands don't inherit it, clear that flag in
+            // this command's child only, between fork and exec
+            unsafe {
+                command.pre_exec(move || {
+                    if libc::fcntl(memfd.as_raw_fd(), libc::F_SETFD, 0) == -1 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(())
+                });
+            }
+        }
+        command
+    }
+
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            memfd: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_warnings(mut self, warnings: Vec<Diagnostic>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+}
+
+/// Embed a hook library compiled by `rhook-build` in the program
+///
+/// It expands to a [HookLibrary::from_bytes] call with the library named `$name` that
+/// `rhook_build::compile` put in the build script `OUT_DIR`, so nothing needs to be compiled at
+/// runtime
+///
+/// Pass `memfd` as second argument to load the library with [HookLibrary::memfd] instead
+///
+/// ```ignore
+/// // OUT_DIR is only set for crates with a build script
+/// use std::process::Command;
+///
+/// let hooks = rhook::embed!("cat_hooks").unwrap();
+/// hooks.attach(Command::new("cat").arg("Cargo.toml")).status().unwrap();
+/// ```
+#[macro_export]
+macro_rules! embed {
+    ($name: expr) => {
+        $crate::HookLibrary::from_bytes(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/",
+            $name,
+            ".so"
+        )))
+    };
+    ($name: expr, memfd) => {
+        $crate::HookLibrary::memfd(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/",
+            $name,
+            ".so"
+        )))
+    };
+}
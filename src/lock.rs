@@ -0,0 +1,45 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// The file locked in the workspace
+const LOCK_FILE: &str = ".lock";
+
+/// An exclusive lock on the hook workspace
+///
+/// It is an `flock` on a file inside the workspace, unlike a `Mutex` it also synchronizes
+/// builds started by different processes. The lock is released when this struct is dropped.
+pub(crate) struct WorkspaceLock {
+    file: File,
+}
+
+impl WorkspaceLock {
+    /// Block until the lock of the given workspace is acquired
+    pub(crate) fn acquire(workspace: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(workspace.join(LOCK_FILE))?;
+
+        loop {
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                return Ok(Self { file });
+            }
+            let error = io::Error::last_os_error();
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+    }
+}
+
+impl Drop for WorkspaceLock {
+    fn drop(&mut self) {
+        // closing the file would release the lock too, but be explicit about it
+        unsafe {
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
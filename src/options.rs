@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use crate::Error;
+
+/// Settings used to build the hook library of an [Anchor](crate::Anchor)
+///
+/// They are set with the builder methods of [Anchor](crate::Anchor)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Options {
+    /// Directory where the hook libraries are built, see [crate::workspace::resolve]
+    pub(crate) workspace: Option<PathBuf>,
+    pub(crate) backend: Backend,
+    pub(crate) profile: Profile,
+    pub(crate) opt_level: Option<String>,
+    pub(crate) rustflags: Vec<String>,
+    /// A rustup toolchain, passed as `+toolchain` to cargo and rustc
+    pub(crate) toolchain: Option<String>,
+    pub(crate) edition: Option<String>,
+    pub(crate) duplicates: Duplicates,
+    /// Call the versions of the original functions the program needs, see
+    /// [crate::Anchor::detect_versions]
+    pub(crate) detect_versions: bool,
+}
+
+impl Options {
+    /// The edition of the generated crate
+    pub(crate) fn edition(&self) -> &str {
+        self.edition.as_deref().unwrap_or("2018")
+    }
+
+    /// A cargo or rustc command using the selected toolchain
+    pub(crate) fn command(&self, program: &str) -> Command {
+        let mut command = Command::new(program);
+        if let Some(toolchain) = &self.toolchain {
+            command.arg(format!("+{}", toolchain));
+        }
+        command
+    }
+
+    /// Run a command created by [Options::command] and collect its output
+    ///
+    /// A missing program or toolchain is reported as [Error::ToolchainNotFound], a failing command
+    /// is not an error here, the caller decides what to do with its output
+    pub(crate) fn output(&self, program: &str, command: &mut Command) -> Result<Output, Error> {
+        let output = match command.output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::ToolchainNotFound(program.to_string()))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(toolchain) = &self.toolchain {
+            // rustup's message when the toolchain is missing
+            if !output.status.success()
+                && String::from_utf8_lossy(&output.stderr).contains("is not installed")
+            {
+                return Err(Error::ToolchainNotFound(format!(
+                    "{} (toolchain {})",
+                    program, toolchain
+                )));
+            }
+        }
+        Ok(output)
+    }
+
+    /// The part of the cache key that depends on these options
+    ///
+    /// The workspace is left out since it doesn't change the built library
+    pub(crate) fn key(&self) -> String {
+        format!(
+            "{:?} {:?} {:?} {:?} {:?} {}",
+            self.backend,
+            self.profile,
+            self.opt_level,
+            self.rustflags,
+            self.toolchain,
+            self.edition()
+        )
+    }
+}
+
+/// Whether `edition` writes unsafe attributes and extern blocks with `unsafe`, like
+/// `#[unsafe(no_mangle)]`
+///
+/// They are required since the 2024 edition, older compilers don't know the syntax
+pub(crate) fn unsafe_attributes(edition: &str) -> bool {
+    matches!(edition.parse::<u32>(), Ok(year) if year >= 2024)
+}
+
+/// The profile the hook library is built with
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Unoptimized with debug info, this is the default
+    #[default]
+    Debug,
+    /// Optimized, use it for hooks on hot functions such as `read`, `write` or `malloc`
+    Release,
+}
+
+/// The tool used to compile the hook library
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Generate a crate and build it with `cargo build`, this is the default
+    #[default]
+    Cargo,
+    /// Compile a single source file with `rustc --crate-type cdylib`
+    ///
+    /// It is faster than cargo and only needs `rustc` at runtime. The libc crate isn't used, the
+    /// library declares the types and functions of the hookable signatures itself, so hooks can
+    /// call `libc::write` or take a `*mut stat` but the structs are opaque and the libc constants
+    /// are missing, use [Backend::Cargo] for hooks that need them
+    Rustc,
+}
+
+/// What to do when several hooks are added on the same function
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Duplicates {
+    /// Run them as a chain, this is the default
+    ///
+    /// The hook added first runs first, and its `original_$libcfn` calls the next hook instead of
+    /// the libc function. A hook returning `None` calls the next hook too. Only the last hook of the
+    /// chain calls the libc function
+    #[default]
+    Chain,
+    /// Fail with [Error::DuplicateHook](crate::Error::DuplicateHook)
+    Reject,
+}
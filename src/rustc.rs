@@ -0,0 +1,97 @@
+//! The rustc backend, it compiles the hook library with a single rustc invocation instead of going
+//! through cargo
+//!
+//! It doesn't use the libc crate either, the library has its own `libc` module, see
+//! [ctypes](crate::ctypes)
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::SourceMap;
+use crate::options::{Options, Profile};
+use crate::{BuildError, Diagnostic, Error, Result, SCAFFOLD};
+
+/// Create the source file of the hook library and write the scaffold to it, return its path
+pub(crate) fn prepare(dir: &Path) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let source = dir.join("lib.rs");
+    std::fs::write(&source, SCAFFOLD)?;
+    Ok(source)
+}
+
+/// Build the dynamic library, and return the path of the built library and the warnings
+pub(crate) fn build_dylib(
+    dir: &Path,
+    key: &str,
+    options: &Options,
+    source_map: &SourceMap,
+) -> Result<(PathBuf, Vec<Diagnostic>)> {
+    let library = dir.join("target").join("librhookdyl.so");
+    let warnings = run("link", &library, dir, key, options, source_map)?;
+    Ok((library, warnings))
+}
+
+/// Type-check the dynamic library without building it, return the warnings
+pub(crate) fn check_dylib(
+    dir: &Path,
+    key: &str,
+    options: &Options,
+    source_map: &SourceMap,
+) -> Result<Vec<Diagnostic>> {
+    let metadata = dir.join("target").join("librhookdyl.rmeta");
+    run("metadata", &metadata, dir, key, options, source_map)
+}
+
+/// Compile the hook library source, return the compiler messages if it succeeds
+fn run(
+    emit: &str,
+    output: &Path,
+    dir: &Path,
+    key: &str,
+    options: &Options,
+    source_map: &SourceMap,
+) -> Result<Vec<Diagnostic>> {
+    std::fs::create_dir_all(dir.join("target"))?;
+
+    let mut command = options.command("rustc");
+    // same defaults as the cargo profiles
+    match options.profile {
+        Profile::Debug => command.args(["-C", "debuginfo=2"]),
+        Profile::Release => command.args(["-C", "opt-level=3"]),
+    };
+    if let Some(opt_level) = &options.opt_level {
+        command.args(["-C", &format!("opt-level={}", opt_level)]);
+    }
+    let mut out = std::ffi::OsString::from(format!("{}=", emit));
+    out.push(output);
+    let result = options.output(
+        "rustc",
+        command
+            .args(["--crate-name", &crate::crate_name(key)])
+            .args(["--crate-type", "cdylib"])
+            .args(["--edition", options.edition()])
+            .args(&options.rustflags)
+            .args(["--error-format", "json"])
+            .args(["--json", "diagnostic-rendered-ansi"])
+            .arg("--emit")
+            .arg(out)
+            .arg(dir.join("lib.rs")),
+    )?;
+
+    // rustc prints one json diagnostic per line on stderr
+    let stderr = String::from_utf8_lossy(&result.stderr).into_owned();
+    let diagnostics = stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|message| source_map.diagnostic(&message))
+        .collect();
+
+    if result.status.success() {
+        Ok(diagnostics)
+    } else {
+        Err(Error::Build(BuildError {
+            diagnostics,
+            stderr,
+        }))
+    }
+}
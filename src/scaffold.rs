@@ -2,6 +2,9 @@
 #![allow(non_camel_case_types)]
 #![allow(dead_code)]
 #![allow(unused_imports)]
+// the 2024 edition warns about unsafe calls in unsafe functions, the hooks and the code around
+// them are unsafe functions
+#![allow(unsafe_op_in_unsafe_fn)]
 
 use libc::*;
 use std::ffi::CString;
@@ -9,3 +12,72 @@ use std::mem::transmute;
 use std::mem::ManuallyDrop;
 
 static mut COUNTER: isize = 0;
+
+/// The original function called by the last hook on `name`, found once and kept in `cache`
+///
+/// `name` and `version` end with a nul byte. A missing function aborts the program with its name
+/// instead of calling a null pointer.
+/// `dlsym` can call hooked functions: glibc before 2.34 allocates with `calloc` the first time a
+/// thread calls it. A hook called while its thread is looking up an original returns `fallback`,
+/// the libc alias of the allocation functions (`__libc_calloc`), or aborts the program when it
+/// is null instead of looking up its own original and recursing until the stack overflows.
+/// The libc functions are called by their path because the hooks, defined at the root of this
+/// crate, shadow the ones imported from libc
+unsafe fn rhook_original(
+    cache: &std::sync::atomic::AtomicPtr<c_void>,
+    name: &str,
+    version: Option<&str>,
+    fallback: *mut c_void,
+) -> *mut c_void {
+    thread_local! {
+        // const and without destructor, it never allocates and is always accessible
+        static RESOLVING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    let original = cache.load(std::sync::atomic::Ordering::Acquire);
+    if !original.is_null() {
+        return original;
+    }
+    if RESOLVING.with(|resolving| resolving.replace(true)) {
+        if fallback.is_null() {
+            rhook_abort(&[
+                "rhook: the hook on ",
+                name.trim_end_matches('\0'),
+                " was called while looking up an original function, its own original can't be \
+                 looked up yet\n",
+            ]);
+        }
+        return fallback;
+    }
+    let original = match version {
+        Some(version) => libc::dlvsym(RTLD_NEXT, name.as_ptr() as _, version.as_ptr() as _),
+        None => libc::dlsym(RTLD_NEXT, name.as_ptr() as _),
+    };
+    RESOLVING.with(|resolving| resolving.set(false));
+    if original.is_null() {
+        let version = version.map_or("", |version| version.trim_end_matches('\0'));
+        rhook_abort(&[
+            "rhook: the original ",
+            name.trim_end_matches('\0'),
+            if version.is_empty() { "" } else { "@" },
+            version,
+            " of the hook was not found in the libraries loaded after the hook library\n",
+        ]);
+    }
+    // threads racing here find the same function
+    cache.store(original, std::sync::atomic::Ordering::Release);
+    original
+}
+
+/// Abort the program with `message` because a hook can't call its original
+#[cold]
+unsafe fn rhook_abort(message: &[&str]) -> ! {
+    // a hooked write whose own original is missing would come back here
+    static REPORTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    if !REPORTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        for part in message {
+            libc::write(2, part.as_ptr() as _, part.len());
+        }
+    }
+    libc::abort()
+}
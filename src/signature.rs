@@ -0,0 +1,283 @@
+//! The signatures of the hookable functions
+//!
+//! The types are kept as they are written in the [libc](https://docs.rs/libc) crate, for example
+//! `*const c_char`, they are resolved in the hook library where `libc::*` is imported
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// The name, parameters and return type of a hooked function
+///
+/// The signatures of the libc functions are static, see [Signature::all] and
+/// [Hook::signature](crate::Hook::signature)
+/// ```
+/// use rhook::Signature;
+///
+/// let read = Signature::of("read").unwrap();
+/// assert_eq!(read.params()[1].name(), "buf");
+/// assert_eq!(read.params()[1].ty(), "*mut c_void");
+/// assert_eq!(read.ret(), "ssize_t");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signature {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) params: Cow<'static, [Param]>,
+    pub(crate) ret: Cow<'static, str>,
+}
+
+/// A parameter of a [Signature]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Param {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) ty: Cow<'static, str>,
+    pub(crate) variadic: bool,
+}
+
+/// The parameters that capture the arguments of a function ending with a plain `...` like printf
+///
+/// On x86_64 the integers and pointers go in the general purpose registers left by the fixed
+/// parameters then on the stack, and the floating point numbers in the 8 vector registers. Keep
+/// in sync with the libc! macro in libcfn.rs
+const VARIADIC_ARGS: &[(&str, &str)] = &[
+    ("arg1", "c_long"),
+    ("arg2", "c_long"),
+    ("arg3", "c_long"),
+    ("arg4", "c_long"),
+    ("arg5", "c_long"),
+    ("arg6", "c_long"),
+    ("arg7", "c_long"),
+    ("arg8", "c_long"),
+    ("arg9", "c_long"),
+    ("arg10", "c_long"),
+    ("arg11", "c_long"),
+    ("arg12", "c_long"),
+    ("float1", "c_double"),
+    ("float2", "c_double"),
+    ("float3", "c_double"),
+    ("float4", "c_double"),
+    ("float5", "c_double"),
+    ("float6", "c_double"),
+    ("float7", "c_double"),
+    ("float8", "c_double"),
+];
+
+impl Signature {
+    /// The name of the function
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The parameters of the function, in order
+    pub fn params(&self) -> &[Param] {
+        &self.params
+    }
+
+    /// The return type of the function, `()` if it returns nothing and `!` if it never returns
+    pub fn ret(&self) -> &str {
+        &self.ret
+    }
+
+    /// Parse the signature of a [custom](crate::Hook::custom) hook: `fn(a: c_int) -> c_int`
+    ///
+    /// Variadic arguments are written `...mode: mode_t, flags: c_int` when their types are known,
+    /// a plain `...` captures them with the parameters of [VARIADIC_ARGS]
+    ///
+    /// This never fails, a broken signature generates a broken function that the compiler
+    /// complains about
+    pub(crate) fn parse(name: &'static str, signature: &str) -> Signature {
+        let signature = signature.trim();
+        let signature = signature
+            .strip_prefix("fn")
+            .unwrap_or(signature)
+            .trim_start();
+        // the parameters are inside the first parentheses, what follows is the return type
+        let (params, ret) = match signature.strip_prefix('(').map(split_at_close) {
+            Some((params, rest)) => (
+                params,
+                rest.trim().strip_prefix("->").unwrap_or("()").trim(),
+            ),
+            None => (signature, "()"),
+        };
+        let mut variadic = false;
+        let mut parsed = Vec::new();
+        for param in split_params(params) {
+            if param == "..." {
+                parsed.extend(VARIADIC_ARGS.iter().map(|(name, ty)| Param {
+                    name: Cow::Borrowed(*name),
+                    ty: Cow::Borrowed(*ty),
+                    variadic: true,
+                }));
+                continue;
+            }
+            let param = match param.strip_prefix("...") {
+                Some(param) => {
+                    variadic = true;
+                    param
+                }
+                None => param,
+            };
+            let (name, ty) = param.split_once(':').unwrap_or((param, ""));
+            parsed.push(Param {
+                name: Cow::Owned(name.trim().to_string()),
+                ty: Cow::Owned(ty.trim().to_string()),
+                variadic,
+            });
+        }
+        let params = parsed;
+        Signature {
+            name: Cow::Borrowed(name),
+            params: Cow::Owned(params),
+            ret: Cow::Owned(if ret.is_empty() { "()" } else { ret }.to_string()),
+        }
+    }
+}
+
+impl Param {
+    /// The name of the parameter, hooks use it to access the argument
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type of the parameter
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// Whether the parameter captures a variadic argument, like the `mode` of `open`
+    ///
+    /// The hook receives every variadic parameter, the ones the caller didn't pass hold whatever
+    /// was left in their registers
+    pub fn is_variadic(&self) -> bool {
+        self.variadic
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}(", self.name)?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            // the variadic parameters follow a single `...`
+            let first_variadic = param.variadic && (i == 0 || !self.params[i - 1].variadic);
+            if first_variadic {
+                write!(f, "...")?;
+            }
+            write!(f, "{}: {}", param.name, param.ty)?;
+        }
+        write!(f, ") -> {}", self.ret)
+    }
+}
+
+/// Split `s` after the parenthesis that closes an already open one
+fn split_at_close(s: &str) -> (&str, &str) {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return (&s[..i], &s[i + 1..]),
+            ')' => depth -= 1,
+            _ => (),
+        }
+    }
+    (s, "")
+}
+
+/// Split function parameters on the commas that aren't inside a type
+fn split_params(params: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut previous = ' ';
+    for (i, c) in params.char_indices() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            // the arrow of a function pointer type doesn't close anything
+            '>' if previous == '-' => (),
+            ')' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(params[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+        previous = c;
+    }
+    split.push(params[start..].trim());
+    split.retain(|param| !param.is_empty());
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_pointer_params_are_not_split() {
+        let params = r#"cb: Option<unsafe extern "C" fn(c_int) -> c_int>, ..."#;
+        assert_eq!(
+            split_params(params),
+            [r#"cb: Option<unsafe extern "C" fn(c_int) -> c_int>"#, "..."]
+        );
+        assert_eq!(
+            split_params("f: fn(a: c_int, b: c_int) -> c_int, n: [c_int; 2],"),
+            ["f: fn(a: c_int, b: c_int) -> c_int", "n: [c_int; 2]"]
+        );
+    }
+
+    #[test]
+    fn parse_function_pointer_and_variadic() {
+        let signature = Signature::parse(
+            "call",
+            r#"fn(cb: Option<unsafe extern "C" fn(c_int) -> c_int>, ...) -> c_int"#,
+        );
+        assert_eq!(signature.name(), "call");
+        assert_eq!(signature.ret(), "c_int");
+        let cb = &signature.params()[0];
+        assert_eq!(cb.name(), "cb");
+        assert_eq!(cb.ty(), r#"Option<unsafe extern "C" fn(c_int) -> c_int>"#);
+        assert!(!cb.is_variadic());
+        assert_eq!(signature.params().len(), 1 + VARIADIC_ARGS.len());
+        assert!(signature.params()[1..].iter().all(Param::is_variadic));
+    }
+
+    #[test]
+    fn parse_plain_variadic() {
+        let signature = Signature::parse("log", "fn(...)");
+        assert_eq!(signature.ret(), "()");
+        let params: Vec<_> = signature
+            .params()
+            .iter()
+            .map(|param| (param.name(), param.ty(), param.is_variadic()))
+            .collect();
+        let expected: Vec<_> = VARIADIC_ARGS
+            .iter()
+            .map(|&(name, ty)| (name, ty, true))
+            .collect();
+        assert_eq!(params, expected);
+        assert_eq!(
+            signature.to_string(),
+            format!(
+                "fn log(...{}) -> ()",
+                VARIADIC_ARGS
+                    .iter()
+                    .map(|(name, ty)| format!("{}: {}", name, ty))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        );
+    }
+
+    #[test]
+    fn parse_named_variadic() {
+        let signature = Signature::parse(
+            "open",
+            "fn(path: *const c_char, flags: c_int, ...mode: mode_t) -> c_int",
+        );
+        let variadic: Vec<_> = signature.params().iter().map(Param::is_variadic).collect();
+        assert_eq!(variadic, [false, false, true]);
+        assert_eq!(signature.params()[2].name(), "mode");
+        assert_eq!(signature.params()[2].ty(), "mode_t");
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+/// Find the newest libc 0.2 source in the local cargo registry
+///
+/// rhook itself depends on libc, so its source is there on any machine that built rhook. Building
+/// the hook library against it means the build never needs to access the registry
+pub(crate) fn libc_source() -> Option<PathBuf> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))?;
+
+    let mut newest: Option<(u32, PathBuf)> = None;
+    // there is one directory per registry index
+    for index in std::fs::read_dir(cargo_home.join("registry").join("src")).ok()? {
+        let index = match index {
+            Ok(index) => index.path(),
+            Err(_) => continue,
+        };
+        let krates = match std::fs::read_dir(&index) {
+            Ok(krates) => krates,
+            Err(_) => continue,
+        };
+        for krate in krates.flatten() {
+            let patch = match krate
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("libc-0.2."))
+                .and_then(|patch| patch.parse::<u32>().ok())
+            {
+                Some(patch) => patch,
+                None => continue,
+            };
+            let path = krate.path();
+            // skip sources that were only partially extracted
+            if !path.join("src").join("lib.rs").exists() {
+                continue;
+            }
+            if newest.as_ref().is_none_or(|(newest, _)| patch > *newest) {
+                newest = Some((patch, path));
+            }
+        }
+    }
+    newest.map(|(_, path)| path)
+}
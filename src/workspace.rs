@@ -0,0 +1,70 @@
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the default workspace
+pub(crate) const RHOOK_DIR: &str = "RHOOK_DIR";
+
+/// Find the directory where the hook libraries are built
+///
+/// In order of priority: the directory set on the Anchor, `$RHOOK_DIR` and `rhookdyl-$UID` inside
+/// the temporary directory (which respects `$TMPDIR`)
+///
+/// The path is made absolute since the libraries built there end up in `LD_PRELOAD`, and the
+/// commands might run in another directory
+pub(crate) fn resolve(dir: Option<&Path>) -> PathBuf {
+    let dir = if let Some(dir) = dir {
+        dir.to_path_buf()
+    } else if let Some(dir) = std::env::var_os(RHOOK_DIR) {
+        PathBuf::from(dir)
+    } else {
+        std::env::temp_dir().join(format!("rhookdyl-{}", unsafe { libc::geteuid() }))
+    };
+    match std::env::current_dir() {
+        Ok(current_dir) => current_dir.join(dir),
+        Err(_) => dir,
+    }
+}
+
+/// Create the workspace if needed and make sure it is private
+///
+/// The hook libraries end up in `LD_PRELOAD`, so a workspace that another user can write to would
+/// let them run their code inside our commands
+pub(crate) fn create(dir: &Path) -> io::Result<()> {
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Err(e) if e.kind() != io::ErrorKind::AlreadyExists => return Err(e),
+        _ => (),
+    }
+    check(dir)
+}
+
+/// Refuse workspaces that are not directories, are owned by another user or are writable by
+/// group or others
+pub(crate) fn check(dir: &Path) -> io::Result<()> {
+    let insecure = |reason: &str| {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "refusing to use {} as the rhook workspace: {}",
+                dir.display(),
+                reason
+            ),
+        ))
+    };
+
+    // don't follow symlinks, the link itself could be planted by someone else
+    let metadata = std::fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() {
+        return insecure("it is not a directory");
+    }
+    if metadata.uid() != unsafe { libc::geteuid() } {
+        return insecure("it is owned by another user");
+    }
+    if metadata.mode() & 0o022 != 0 {
+        return insecure("it is writable by group or others");
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Several threads can build the same hooks to the same destination
+
+use rhook::{Hook, RunHook};
+use std::process::Command;
+
+#[test]
+fn build_to_in_many_threads() {
+    let dir = std::env::temp_dir().join(format!("rhook-build-to-{}", std::process::id()));
+    let destination = dir.join("hooks.so");
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let (dir, destination) = (dir.clone(), destination.clone());
+            std::thread::spawn(move || {
+                for _ in 0..20 {
+                    Command::new("true")
+                        .add_hook(Hook::read(stringify!(|| None)))
+                        .workspace(dir.join("workspace"))
+                        .build_to(&destination)
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // only the library is left next to the destination, no temporary file
+    let files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files.len(), 2, "{:?}", files);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
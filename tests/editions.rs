@@ -0,0 +1,34 @@
+//! The hook library builds without warnings in every edition the host compiler supports
+
+use rhook::{Backend, Hook, RunHook};
+use std::process::Command;
+
+#[test]
+fn hooks_build_in_every_edition() {
+    let workspace = std::env::temp_dir().join(format!("rhook-editions-{}", std::process::id()));
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        for edition in ["2018", "2021", "2024"] {
+            let checked = Command::new("true")
+                .add_hook(Hook::read(stringify!(|| None)))
+                .add_hook(Hook::write(stringify!(|| ())).before())
+                .add_hook(Hook::open(stringify!(|| ())).after())
+                .add_hook(Hook::printf(stringify!(|| None)))
+                .add_hook(Hook::malloc(stringify!(|| None)))
+                .workspace(&workspace)
+                .edition(edition)
+                .backend(backend)
+                .check_hooks();
+            match checked {
+                Ok(warnings) => assert!(
+                    warnings.is_empty(),
+                    "edition {} with {:?}: {:?}",
+                    edition,
+                    backend,
+                    warnings
+                ),
+                Err(error) => panic!("edition {} with {:?}: {}", edition, backend, error),
+            }
+        }
+    }
+    std::fs::remove_dir_all(&workspace).unwrap();
+}
@@ -0,0 +1,32 @@
+//! Embedded libraries can be written by several threads at once
+
+use rhook::HookLibrary;
+
+#[test]
+fn from_bytes_in_many_threads() {
+    let workspace = std::env::temp_dir().join(format!("rhook-embedded-{}", std::process::id()));
+    // the embedded libraries go to the default workspace
+    std::env::set_var("RHOOK_DIR", &workspace);
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for round in 0..200u32 {
+                    let bytes = round.to_le_bytes().repeat(1 << 14);
+                    let library = HookLibrary::from_bytes(&bytes).unwrap();
+                    assert_eq!(std::fs::read(library.path()).unwrap(), bytes);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // only the libraries are left, no temporary file
+    let files = std::fs::read_dir(workspace.join("embedded"))
+        .unwrap()
+        .count();
+    assert_eq!(files, 200);
+    std::fs::remove_dir_all(&workspace).unwrap();
+}
@@ -0,0 +1,60 @@
+//! The hooks are defined at the root of the hook library, next to the code rhook generates
+//! around them: hooking a function that code calls must still build
+
+use rhook::{Backend, Error, Hook, RunHook};
+use std::process::Command;
+
+fn check(backend: Backend, hook: Hook) -> Result<(), Error> {
+    let workspace = std::env::temp_dir().join(format!(
+        "rhook-shadowing-{}-{:?}-{}",
+        hook.name(),
+        backend,
+        std::process::id()
+    ));
+    let checked = Command::new("true")
+        .add_hook(hook)
+        // an after hook saves errno with __errno_location
+        .add_hook(Hook::read(stringify!(|| ())).after())
+        .workspace(&workspace)
+        .backend(backend)
+        .check_hooks()
+        .map(|_| ());
+    // the rejected hooks never create it
+    let _ = std::fs::remove_dir_all(&workspace);
+    checked
+}
+
+#[test]
+fn hooks_on_functions_used_by_the_hook_library_build() {
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        let hooks = [
+            Hook::syscall(stringify!(|| None)),
+            Hook::abort(stringify!(|| ())).before(),
+            Hook::write(stringify!(|| None)),
+            Hook::__errno_location(stringify!(|| None)),
+        ];
+        for hook in hooks {
+            let name = hook.name();
+            if let Err(error) = check(backend, hook) {
+                panic!("{} with {:?}: {}", name, backend, error);
+            }
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn hooks_on_dlsym_are_rejected() {
+    assert!(!Hook::names().any(|name| name == "dlsym" || name == "dlvsym"));
+    assert!(Hook::from_name("dlsym", "|| None").is_err());
+    for hook in [
+        Hook::dlsym(stringify!(|| None)),
+        Hook::dlvsym(stringify!(|| None)),
+    ] {
+        let name = hook.name();
+        match check(Backend::Cargo, hook) {
+            Err(Error::Reserved(reserved)) => assert_eq!(reserved, name),
+            other => panic!("{}: {:?}", name, other.err()),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Hooks on variadic functions forward the variadic arguments of the program to the original
+//! function and see them as parameters
+
+use rhook::{Backend, Hook, RunHook};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// the hooked program, built with gcc since Rust programs can't call variadic functions without
+// the libc crate
+const PROGRAM: &str = r#"
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+
+int main(int argc, char **argv) {
+    if (argc == 3 && strcmp(argv[1], "open") == 0) {
+        umask(0);
+        return open(argv[2], O_CREAT | O_WRONLY, 0640) < 0;
+    }
+    printf("%d %d %d %d %d %d %d %.1f %.1f\n", 1, 2, 3, 4, 5, 6, 7, 1.5, 2.5);
+    return 0;
+}
+"#;
+
+/// A workspace for the hook libraries and the hooked program, removed when dropped
+struct Workspace(PathBuf);
+
+impl Workspace {
+    fn new(test: &str) -> Workspace {
+        let path =
+            std::env::temp_dir().join(format!("rhook-variadic-{}-{}", test, std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        let source = path.join("program.c");
+        std::fs::write(&source, PROGRAM).unwrap();
+        // without fortify and builtins gcc keeps the calls to open and printf
+        let status = Command::new("gcc")
+            .args(["-O0", "-fno-builtin", "-U_FORTIFY_SOURCE", "-o"])
+            .arg(path.join("program"))
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        Workspace(path)
+    }
+
+    fn run(&self, backend: Backend, hooks: Vec<Hook>, args: &[&Path]) -> Output {
+        let mut command = Command::new(self.0.join("program"));
+        command
+            .args(args)
+            .add_hooks(hooks)
+            .workspace(&self.0)
+            .backend(backend)
+            .set_hooks()
+            .unwrap();
+        let output = command.output().unwrap();
+        assert!(output.status.success(), "{:?}: {:?}", backend, output);
+        output
+    }
+
+    /// The mode of the file created by the program with `hooks` on `open`
+    fn created_mode(&self, backend: Backend, hooks: Vec<Hook>) -> u32 {
+        let file = self.0.join(format!("created-{:?}", backend));
+        let _ = std::fs::remove_file(&file);
+        self.run(backend, hooks, &[Path::new("open"), &file]);
+        std::fs::metadata(&file).unwrap().permissions().mode() & 0o777
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn open_forwards_the_mode() {
+    let workspace = Workspace::new("open");
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        let hooks = vec![Hook::open(stringify!(|| None))];
+        assert_eq!(workspace.created_mode(backend, hooks), 0o640);
+    }
+}
+
+#[test]
+fn open_chain_forwards_the_mode() {
+    let workspace = Workspace::new("open-chain");
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        let hooks = vec![
+            Hook::open(stringify!(|| None)),
+            Hook::open(stringify!(|| Some(original_open(path, oflag, mode)))),
+        ];
+        assert_eq!(workspace.created_mode(backend, hooks), 0o640);
+    }
+}
+
+#[test]
+fn before_hook_rewrites_the_mode() {
+    let workspace = Workspace::new("open-before");
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        let hooks = vec![Hook::open(stringify!(|| mode = 0o600)).before()];
+        assert_eq!(workspace.created_mode(backend, hooks), 0o600);
+    }
+}
+
+#[test]
+fn printf_forwards_integers_and_floats() {
+    let workspace = Workspace::new("printf");
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        let output = workspace.run(backend, vec![Hook::printf(stringify!(|| None))], &[]);
+        assert_eq!(output.stdout, b"1 2 3 4 5 6 7 1.5 2.5\n");
+
+        // the integers after the fifth one are on the stack
+        let hooks = vec![Hook::printf(stringify!(|| {
+            arg7 *= 10;
+            float2 *= 2.0;
+        }))
+        .before()];
+        let output = workspace.run(backend, hooks, &[]);
+        assert_eq!(output.stdout, b"1 2 3 4 5 6 70 1.5 5.0\n");
+    }
+}
@@ -0,0 +1,38 @@
+//! The warnings of a build are reported by the library it produces
+
+use rhook::{Backend, Hook, RunHook};
+use std::process::Command;
+
+#[test]
+fn build_reports_the_warnings_once() {
+    for backend in [Backend::Cargo, Backend::Rustc] {
+        let workspace = std::env::temp_dir().join(format!(
+            "rhook-warnings-{:?}-{}",
+            backend,
+            std::process::id()
+        ));
+        let build = || {
+            Command::new("true")
+                .add_hook(Hook::read(stringify!(|| {
+                    let unused = 1;
+                    None
+                })))
+                .workspace(&workspace)
+                .backend(backend)
+                .build()
+                .unwrap()
+        };
+
+        let warnings = build().warnings().to_vec();
+        assert!(
+            warnings.iter().any(|warning| warning.hook == Some("read")),
+            "{:?}: {:?}",
+            backend,
+            warnings
+        );
+        // the second build comes from the cache
+        assert!(build().warnings().is_empty());
+
+        std::fs::remove_dir_all(&workspace).unwrap();
+    }
+}